@@ -0,0 +1,13 @@
+use encipher::{cramer_shoup::CramerShoupKeyPair, PrivateKey, PublicKey};
+
+fn main() {
+    let message = b"Hello World";
+
+    let key = CramerShoupKeyPair::new(128, 10).unwrap();
+    println!("{:#?}\n", key.public);
+
+    let encrypted = key.encrypt_chunked(message).unwrap();
+    let decrypted = key.decrypt_chunked(&encrypted).unwrap();
+    println!("{encrypted:?}\n");
+    println!("{:#?}\n", String::from_utf8_lossy(&decrypted));
+}
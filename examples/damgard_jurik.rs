@@ -0,0 +1,23 @@
+use encipher::{damgard_jurik::DamgardJurikKeyPair, PrivateKey, PublicKey};
+
+fn main() {
+    let message = b"Hello World";
+
+    let key = DamgardJurikKeyPair::new(128, 3, 10).unwrap();
+    println!("{:#?}\n", key.public);
+
+    let encrypted = key.encrypt_chunked(message).unwrap();
+    let decrypted = key.decrypt_chunked(&encrypted).unwrap();
+    println!("{encrypted:?}\n");
+    println!("{:#?}\n", String::from_utf8_lossy(&decrypted));
+
+    // Any two of three parties can decrypt a block together.
+    let shares = key.private.share(2, 3).unwrap();
+    let ciphertext = key.public.encrypt_block(message).unwrap();
+    let partials = [
+        shares[0].decrypt_partial(&ciphertext).unwrap(),
+        shares[2].decrypt_partial(&ciphertext).unwrap(),
+    ];
+    let combined = key.public.combine_partials(&partials).unwrap();
+    println!("{:#?}", String::from_utf8_lossy(&combined));
+}
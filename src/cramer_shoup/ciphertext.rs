@@ -0,0 +1,53 @@
+use ibig::UBig;
+
+use crate::result::{Error, Result};
+use crate::utils::{marshal_bytes, tag_bytes, unmarshal_bytes, untag_bytes, TypeTag};
+
+/// A single block encrypted with a Cramer–Shoup public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CramerShoupCiphertext {
+    /// The ephemeral value `g1 ^ k mod p`.
+    pub u1: UBig,
+
+    /// The ephemeral value `g2 ^ k mod p`.
+    pub u2: UBig,
+
+    /// The masked message `m * h ^ k mod p`.
+    pub e: UBig,
+
+    /// The validity tag `c ^ k * d ^ (k * alpha) mod p`.
+    pub v: UBig,
+}
+
+impl CramerShoupCiphertext {
+    /// Encodes all four components as marshalled little-endian bytes behind the type tag.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let components = marshal_bytes(&vec![
+            self.u1.to_le_bytes(),
+            self.u2.to_le_bytes(),
+            self.e.to_le_bytes(),
+            self.v.to_le_bytes(),
+        ]);
+        tag_bytes(TypeTag::CramerShoupCiphertext, &components)
+    }
+
+    /// Decodes a ciphertext produced by [`CramerShoupCiphertext::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let [u1, u2, e, v] = <[Vec<u8>; 4]>::try_from(unmarshal_bytes(untag_bytes(
+            TypeTag::CramerShoupCiphertext,
+            bytes,
+        )?)?)
+        .map_err(|_| Error::MalformedInput {
+            reason: "expected exactly four components",
+            chunk: None,
+        })?;
+
+        Ok(Self {
+            u1: UBig::from_le_bytes(&u1),
+            u2: UBig::from_le_bytes(&u2),
+            e: UBig::from_le_bytes(&e),
+            v: UBig::from_le_bytes(&v),
+        })
+    }
+}
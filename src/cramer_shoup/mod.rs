@@ -0,0 +1,46 @@
+mod ciphertext;
+mod pair;
+mod private;
+mod public;
+
+use ibig::UBig;
+use sha2::{Digest, Sha256};
+
+use crate::elgamal::{in_subgroup, ElGamalParams};
+use crate::result::{Algorithm, Error, Result};
+use crate::utils::{bits_to_int, to_fixed_be_bytes};
+
+pub use ciphertext::CramerShoupCiphertext;
+pub use pair::CramerShoupKeyPair;
+pub use private::CramerShoupPrivate;
+pub use public::CramerShoupPublic;
+
+/// Ensures that `value` lies in the prime-order subgroup described by `params`.
+fn check_subgroup(value: &UBig, params: &ElGamalParams, reason: &'static str) -> Result<()> {
+    match in_subgroup(value, &params.p, &params.q) {
+        true => Ok(()),
+        false => Err(Error::OutOfRange {
+            algorithm: Algorithm::CramerShoup,
+            reason,
+        }),
+    }
+}
+
+/// Hashes `u1`, `u2` and `e` into the exponent `alpha` of the validity tag.
+///
+/// Every element is encoded as a big-endian number as wide as `p`, so the
+/// encoding is unambiguous, and the SHA-256 digest is truncated to the bit
+/// length of `q` and reduced modulo `q`.
+fn tag_exponent(params: &ElGamalParams, u1: &UBig, u2: &UBig, e: &UBig) -> UBig {
+    let width = params.p.bit_len().div_ceil(8);
+
+    let mut hasher = Sha256::new();
+    for element in [u1, u2, e] {
+        hasher.update(to_fixed_be_bytes(element, width));
+    }
+
+    bits_to_int(&hasher.finalize(), params.q.bit_len()) % &params.q
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,135 @@
+use ibig::{ubig, UBig};
+use rand::{CryptoRng, Rng, RngCore};
+
+use super::{CramerShoupPrivate, CramerShoupPublic};
+use crate::elgamal::ElGamalParams;
+use crate::result::{Algorithm, Result};
+use crate::utils::PowMod;
+use crate::{PrivateKey, PublicKey};
+
+/// A key pair for the Cramer–Shoup cryptosystem.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CramerShoupKeyPair {
+    /// The public key for encryption.
+    pub public: CramerShoupPublic,
+
+    /// The private key for decryption.
+    pub private: CramerShoupPrivate,
+}
+
+impl CramerShoupKeyPair {
+    /// Generates a new Cramer–Shoup key pair with the specified bit length and persistence level.
+    ///
+    /// Fresh domain parameters are generated for the pair; prefer
+    /// [`CramerShoupKeyPair::from_params`] when many keys are needed.
+    ///
+    /// * `bit_length`: The desired bit length of the group modulus.
+    /// * `persistence`: The number of iterations for checking numbers for primality.
+    ///
+    /// Returns a newly generated `CramerShoupKeyPair` instance, or
+    /// [`Error::KeyGeneration`](crate::result::Error::KeyGeneration) if `bit_length` is too small.
+    #[inline]
+    pub fn new(bit_length: usize, persistence: usize) -> Result<Self> {
+        Self::new_with_rng(bit_length, persistence, &mut rand::thread_rng())
+    }
+
+    /// Generates a new Cramer–Shoup key pair, drawing all randomness from `rng`.
+    ///
+    /// The same seeded generator always yields the same key pair.
+    pub fn new_with_rng<R: RngCore + CryptoRng>(
+        bit_length: usize,
+        persistence: usize,
+        rng: &mut R,
+    ) -> Result<Self> {
+        let params = ElGamalParams::generate_with_rng(bit_length, persistence, rng)?;
+        Ok(Self::from_params_with_rng(params, rng))
+    }
+
+    /// Generates a key pair in an existing group, such as one of the named ElGamal groups.
+    #[inline]
+    pub fn from_params(params: ElGamalParams) -> Self {
+        Self::from_params_with_rng(params, &mut rand::thread_rng())
+    }
+
+    /// Generates a key pair in an existing group, drawing the private key from `rng`.
+    pub fn from_params_with_rng<R: RngCore + CryptoRng>(
+        params: ElGamalParams,
+        rng: &mut R,
+    ) -> Self {
+        let ElGamalParams { p, q, g } = &params;
+        let mut exponent = |low: UBig| rng.gen_range(low..q.clone());
+
+        // Nobody may know log_g(g2), so it is picked at random and forgotten.
+        let g2 = g.powmod(exponent(ubig!(1)), p);
+        let [x1, x2, y1, y2] = [(); 4].map(|_| exponent(ubig!(0)));
+        let z = exponent(ubig!(1));
+
+        let combine = |a: &UBig, b: &UBig| (g.powmod(a.clone(), p) * g2.powmod(b.clone(), p)) % p;
+        let c = combine(&x1, &x2);
+        let d = combine(&y1, &y2);
+        let h = g.powmod(z.clone(), p);
+
+        Self {
+            public: CramerShoupPublic {
+                params: params.clone(),
+                g2,
+                c,
+                d,
+                h,
+            },
+
+            private: CramerShoupPrivate {
+                params,
+                x1: x1.into(),
+                x2: x2.into(),
+                y1: y1.into(),
+                y2: y2.into(),
+                z: z.into(),
+            },
+        }
+    }
+}
+
+impl PrivateKey for CramerShoupKeyPair {
+    #[inline]
+    fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.private.decrypt(message)
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::CramerShoup
+    }
+}
+
+impl PublicKey for CramerShoupKeyPair {
+    #[inline]
+    fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        self.public.encrypt(bytes)
+    }
+
+    #[inline]
+    fn encrypt_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        self.public.encrypt_with_rng(bytes, rng)
+    }
+
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.public.bit_length()
+    }
+
+    #[inline]
+    fn max_message_size(&self) -> usize {
+        self.public.max_message_size()
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::CramerShoup
+    }
+}
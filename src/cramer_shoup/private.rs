@@ -0,0 +1,99 @@
+use ibig::{ubig, UBig};
+
+use super::{check_subgroup, tag_exponent, CramerShoupCiphertext};
+use crate::elgamal::ElGamalParams;
+use crate::result::{Algorithm, Error};
+use crate::secret::SecretUBig;
+use crate::utils::{ct_eq, ct_powmod, ct_select, to_fixed_be_bytes};
+use crate::{keypair::PrivateKey, result::Result};
+
+/// Private key for the Cramer–Shoup cryptosystem.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CramerShoupPrivate {
+    /// Domain parameters of the group the key lives in.
+    pub params: ElGamalParams,
+
+    /// Secret exponent `x1` in `[0, q)` behind `c`.
+    pub x1: SecretUBig,
+
+    /// Secret exponent `x2` in `[0, q)` behind `c`.
+    pub x2: SecretUBig,
+
+    /// Secret exponent `y1` in `[0, q)` behind `d`.
+    pub y1: SecretUBig,
+
+    /// Secret exponent `y2` in `[0, q)` behind `d`.
+    pub y2: SecretUBig,
+
+    /// Secret exponent `z` in `[1, q)` behind `h`.
+    pub z: SecretUBig,
+}
+
+impl CramerShoupPrivate {
+    /// Decrypts a single typed ciphertext block.
+    #[inline]
+    pub fn decrypt_block(&self, ciphertext: &CramerShoupCiphertext) -> Result<Vec<u8>> {
+        self.params.decode(&self.decrypt_element(ciphertext)?)
+    }
+
+    /// Decrypts a ciphertext into the group element it encrypts, without decoding it.
+    ///
+    /// Fails with [`Error::Verification`] if the validity tag does not match. The tag
+    /// is compared in constant time and the message is recovered whether or not it
+    /// matches, so the rejection does not reveal how far the computation got.
+    /// Fails with [`Error::InvalidKey`] if `z` does not lie in `[1, q)`.
+    pub fn decrypt_element(&self, ciphertext: &CramerShoupCiphertext) -> Result<UBig> {
+        let CramerShoupCiphertext { u1, u2, e, v } = ciphertext;
+        let ElGamalParams { p, q, .. } = &self.params;
+
+        let z = self.z.expose();
+        if *z == ubig!(0) || z >= q {
+            return Err(Error::InvalidKey {
+                algorithm: Algorithm::CramerShoup,
+                reason: "secret exponent z is out of range",
+            });
+        }
+
+        check_subgroup(u1, &self.params, "u1 is not in the subgroup")?;
+        check_subgroup(u2, &self.params, "u2 is not in the subgroup")?;
+        check_subgroup(e, &self.params, "e is not in the subgroup")?;
+        check_subgroup(v, &self.params, "v is not in the subgroup")?;
+
+        let alpha = tag_exponent(&self.params, u1, u2, e);
+        let exponent_1 = (self.x1.expose() + self.y1.expose() * &alpha) % q;
+        let exponent_2 = (self.x2.expose() + self.y2.expose() * &alpha) % q;
+        let expected = (ct_powmod(u1, &exponent_1, p) * ct_powmod(u2, &exponent_2, p)) % p;
+
+        // u1 ^ -z is computed as u1 ^ (q - z), as in ElGamal decryption.
+        let mask = ct_powmod(u1, &(q - z), p);
+        let message = (e * mask) % p;
+
+        let width = p.bit_len().div_ceil(8);
+        let valid = ct_eq(
+            &to_fixed_be_bytes(&expected, width),
+            &to_fixed_be_bytes(v, width),
+        );
+        let message = ct_select(&to_fixed_be_bytes(&message, width), &vec![0; width], valid);
+
+        match valid {
+            true => Ok(UBig::from_be_bytes(&message)),
+            false => Err(Error::Verification {
+                algorithm: Algorithm::CramerShoup,
+                chunk: None,
+            }),
+        }
+    }
+}
+
+impl PrivateKey for CramerShoupPrivate {
+    #[inline]
+    fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.decrypt_block(&CramerShoupCiphertext::from_bytes(message)?)
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::CramerShoup
+    }
+}
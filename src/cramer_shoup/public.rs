@@ -0,0 +1,120 @@
+use ibig::{ubig, UBig};
+use rand::{CryptoRng, Rng, RngCore};
+
+use super::{tag_exponent, CramerShoupCiphertext};
+use crate::elgamal::{in_subgroup, ElGamalParams};
+use crate::result::{Algorithm, Error};
+use crate::utils::PowMod;
+use crate::{keypair::PublicKey, result::Result};
+
+/// Public key for the Cramer–Shoup cryptosystem.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CramerShoupPublic {
+    /// Domain parameters of the group the key lives in; `g` serves as `g1`.
+    pub params: ElGamalParams,
+
+    /// Second generator `g2` of the subgroup.
+    pub g2: UBig,
+
+    /// Public value `g1 ^ x1 * g2 ^ x2 mod p`.
+    pub c: UBig,
+
+    /// Public value `g1 ^ y1 * g2 ^ y2 mod p`.
+    pub d: UBig,
+
+    /// Public value `g1 ^ z mod p`.
+    pub h: UBig,
+}
+
+impl CramerShoupPublic {
+    /// Checks that the key is plausible before it is used.
+    ///
+    /// The domain parameters must pass [`ElGamalParams::validate`], `g2` must be
+    /// an element other than `1` of the subgroup they describe, and so must `c`,
+    /// `d` and `h` be members of it.
+    pub fn validate(&self) -> Result<()> {
+        self.params.validate()?;
+
+        let invalid = |reason| {
+            Err(Error::InvalidKey {
+                algorithm: Algorithm::CramerShoup,
+                reason,
+            })
+        };
+
+        let ElGamalParams { p, q, .. } = &self.params;
+        if self.g2 == ubig!(1) || !in_subgroup(&self.g2, p, q) {
+            return invalid("second generator is not in the subgroup");
+        }
+
+        if [&self.c, &self.d, &self.h]
+            .iter()
+            .any(|value| !in_subgroup(value, p, q))
+        {
+            return invalid("public value is not in the subgroup");
+        }
+
+        Ok(())
+    }
+
+    /// Encrypts a single block into a typed ciphertext.
+    #[inline]
+    pub fn encrypt_block(&self, bytes: &[u8]) -> Result<CramerShoupCiphertext> {
+        self.encrypt_block_with_rng(bytes, &mut rand::thread_rng())
+    }
+
+    /// Encrypts a single block into a typed ciphertext, drawing the ephemeral key from `rng`.
+    ///
+    /// The message is encoded into the subgroup with [`ElGamalParams::encode`].
+    pub fn encrypt_block_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<CramerShoupCiphertext> {
+        let message = self.params.encode(bytes)?;
+        let ElGamalParams { p, q, g } = &self.params;
+
+        let k = rng.gen_range(ubig!(1)..q.clone());
+        let u1 = g.powmod(k.clone(), p);
+        let u2 = self.g2.powmod(k.clone(), p);
+        let e = (message * self.h.powmod(k.clone(), p)) % p;
+
+        let alpha = tag_exponent(&self.params, &u1, &u2, &e);
+        let v = (self.c.powmod(k.clone(), p) * self.d.powmod((k * alpha) % q, p)) % p;
+
+        Ok(CramerShoupCiphertext { u1, u2, e, v })
+    }
+}
+
+impl PublicKey for CramerShoupPublic {
+    #[inline]
+    fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        self.encrypt_block(bytes).map(|c| c.to_bytes())
+    }
+
+    #[inline]
+    fn encrypt_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        self.encrypt_block_with_rng(bytes, rng)
+            .map(|c| c.to_bytes())
+    }
+
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.params.p.bit_len()
+    }
+
+    #[inline]
+    fn max_message_size(&self) -> usize {
+        self.params.max_message_size()
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::CramerShoup
+    }
+}
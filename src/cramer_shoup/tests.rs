@@ -0,0 +1,47 @@
+use rand::{rngs::StdRng, SeedableRng};
+
+use super::{CramerShoupCiphertext, CramerShoupKeyPair};
+use crate::elgamal::{ElGamalParams, NamedGroup};
+use crate::keypair::{PrivateKey, PublicKey};
+use crate::result::Error;
+
+const MESSAGE: [u8; 445] = *b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.";
+
+#[test]
+fn test_encrypt_decrypt() {
+    let mut rng = StdRng::seed_from_u64(11);
+    let key = CramerShoupKeyPair::new_with_rng(128, 5, &mut rng).unwrap();
+    assert!(key.public.validate().is_ok());
+
+    let encrypted = key.encrypt_chunked(&MESSAGE).unwrap();
+    let decrypted = key.decrypt_chunked(&encrypted).unwrap();
+    assert_eq!(MESSAGE, decrypted.as_slice());
+
+    let params = ElGamalParams::named(NamedGroup::Modp2048);
+    let key = CramerShoupKeyPair::from_params_with_rng(params, &mut rng);
+    let encrypted = key.encrypt_with_rng(b"Lorem", &mut rng).unwrap();
+    assert_eq!(key.decrypt(&encrypted).unwrap(), b"Lorem");
+}
+
+#[test]
+fn test_rejects_modified_ciphertext() {
+    let key = CramerShoupKeyPair::new(128, 5).unwrap();
+    let ciphertext = key.public.encrypt_block(b"Lorem").unwrap();
+    let p = &key.public.params.p;
+
+    // Multiplying e by a subgroup element keeps every component well formed,
+    // so only the validity tag can catch the change.
+    let mauled = CramerShoupCiphertext {
+        e: (&ciphertext.e * &key.public.params.g) % p,
+        ..ciphertext.clone()
+    };
+    assert!(matches!(
+        key.private.decrypt_block(&mauled),
+        Err(Error::Verification { .. })
+    ));
+
+    let mut bytes = ciphertext.to_bytes();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 1;
+    assert!(key.decrypt(&bytes).is_err());
+}
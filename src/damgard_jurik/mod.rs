@@ -0,0 +1,14 @@
+mod ciphertext;
+mod pair;
+mod private;
+mod public;
+mod threshold;
+
+pub use ciphertext::DamgardJurikCiphertext;
+pub use pair::DamgardJurikKeyPair;
+pub use private::DamgardJurikPrivate;
+pub use public::DamgardJurikPublic;
+pub use threshold::{DamgardJurikKeyShare, DamgardJurikPartialDecryption};
+
+#[cfg(test)]
+mod tests;
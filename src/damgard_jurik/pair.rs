@@ -0,0 +1,113 @@
+use rand::{CryptoRng, RngCore};
+
+use super::{DamgardJurikPrivate, DamgardJurikPublic};
+use crate::result::{Algorithm, Error, Result};
+use crate::utils::{check_prime_bits, gen_prime_pair, imod};
+use crate::{PrivateKey, PublicKey};
+
+/// A key pair for the Damgård–Jurik cryptosystem.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DamgardJurikKeyPair {
+    /// The public key for encryption.
+    pub public: DamgardJurikPublic,
+
+    /// The private key for decryption.
+    pub private: DamgardJurikPrivate,
+}
+
+impl DamgardJurikKeyPair {
+    /// Generates a new Damgård–Jurik key pair with the specified bit length, degree and persistence level.
+    ///
+    /// * `bit_length`: The desired bit length of each of the two primes.
+    /// * `degree`: The degree `s`; plaintexts are taken modulo `n ^ s`.
+    /// * `persistence`: The number of iterations for checking numbers for primality.
+    ///
+    /// Returns a newly generated `DamgardJurikKeyPair` instance, or
+    /// [`Error::KeyGeneration`] if `bit_length` is too small or `degree` is zero.
+    #[inline]
+    pub fn new(bit_length: usize, degree: usize, persistence: usize) -> Result<Self> {
+        Self::new_with_rng(bit_length, degree, persistence, &mut rand::thread_rng())
+    }
+
+    /// Generates a new Damgård–Jurik key pair, drawing all randomness from `rng`.
+    ///
+    /// The same seeded generator always yields the same key pair.
+    pub fn new_with_rng<R: RngCore + CryptoRng>(
+        bit_length: usize,
+        degree: usize,
+        persistence: usize,
+        rng: &mut R,
+    ) -> Result<Self> {
+        check_prime_bits(Algorithm::DamgardJurik, bit_length)?;
+        if degree == 0 {
+            return Err(Error::KeyGeneration {
+                algorithm: Algorithm::DamgardJurik,
+                reason: "degree must be at least 1",
+            });
+        }
+
+        let (p, q) = gen_prime_pair(bit_length, persistence, rng);
+
+        let n = &p * &q;
+        let plaintext_modulus = n.pow(degree);
+
+        let (p1, q1) = (&p - 1u8, &q - 1u8);
+        let lambda = &p1 * &q1 / p1.gcd(&q1);
+        let (_, inverse, _) = lambda.extended_gcd(&plaintext_modulus);
+        let exponent = &lambda * imod(&inverse, &plaintext_modulus);
+
+        Ok(Self {
+            public: DamgardJurikPublic { divisor: n, degree },
+            private: DamgardJurikPrivate {
+                exponent: exponent.into(),
+                prime_1: p.into(),
+                prime_2: q.into(),
+                degree,
+            },
+        })
+    }
+}
+
+impl PrivateKey for DamgardJurikKeyPair {
+    #[inline]
+    fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.private.decrypt(message)
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::DamgardJurik
+    }
+}
+
+impl PublicKey for DamgardJurikKeyPair {
+    #[inline]
+    fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        self.public.encrypt(bytes)
+    }
+
+    #[inline]
+    fn encrypt_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        self.public.encrypt_with_rng(bytes, rng)
+    }
+
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.public.bit_length()
+    }
+
+    #[inline]
+    fn max_message_size(&self) -> usize {
+        self.public.max_message_size()
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::DamgardJurik
+    }
+}
@@ -0,0 +1,98 @@
+use ibig::{ubig, UBig};
+
+use super::DamgardJurikCiphertext;
+use crate::result::{Algorithm, Error};
+use crate::secret::SecretUBig;
+use crate::utils::{ct_powmod, imod, mod_sub};
+use crate::{keypair::PrivateKey, result::Result};
+
+/// Private key for the Damgård–Jurik cryptosystem.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DamgardJurikPrivate {
+    /// Decryption exponent `d` with `d ≡ 0 (mod λ)` and `d ≡ 1 (mod n ^ s)`.
+    ///
+    /// Raising a ciphertext to `d` yields `(1 + n) ^ m` directly, without a separate
+    /// multiplication by `λ⁻¹`, so `d` can be secret-shared for threshold decryption.
+    pub exponent: SecretUBig,
+
+    /// First prime factor of the public modulus.
+    pub prime_1: SecretUBig,
+
+    /// Second prime factor of the public modulus.
+    pub prime_2: SecretUBig,
+
+    /// The degree `s` of the plaintext space `n ^ s`.
+    pub degree: usize,
+}
+
+impl DamgardJurikPrivate {
+    /// Decrypts a single typed ciphertext block.
+    pub fn decrypt_block(&self, ciphertext: &DamgardJurikCiphertext) -> Result<Vec<u8>> {
+        let n = self.prime_1.expose() * self.prime_2.expose();
+        let modulus = n.pow(self.degree + 1);
+
+        if ciphertext.0 >= modulus {
+            return Err(Error::OutOfRange {
+                algorithm: Algorithm::DamgardJurik,
+                reason: "ciphertext is not smaller than n ^ (s + 1)",
+            });
+        }
+
+        if ciphertext.0.gcd(&n) != ubig!(1) {
+            return Err(Error::OutOfRange {
+                algorithm: Algorithm::DamgardJurik,
+                reason: "ciphertext is not invertible",
+            });
+        }
+
+        let power = ct_powmod(&ciphertext.0, self.exponent.expose(), &modulus);
+        Ok(discrete_log(&power, &n, self.degree).to_le_bytes())
+    }
+}
+
+/// Recovers `i` from `(1 + n) ^ i mod n ^ (s + 1)`.
+///
+/// This is the algorithm of Damgård and Jurik, which determines `i` modulo `n ^ j`
+/// for `j = 1, ..., s` in turn from the binomial expansion of `(1 + n) ^ i`.
+pub(super) fn discrete_log(power: &UBig, n: &UBig, degree: usize) -> UBig {
+    let l = |x: UBig| (x - 1u8) / n;
+
+    let mut i = ubig!(0);
+    let mut n_j = n.clone();
+    for j in 1..=degree {
+        let n_next = &n_j * n;
+        let mut t1 = l(power % &n_next);
+        let mut t2 = i.clone();
+
+        let mut factorial = ubig!(1);
+        let mut n_k = ubig!(1);
+        for k in 2..=j {
+            i = mod_sub(&i, &ubig!(1), &n_j);
+            t2 = (t2 * &i) % &n_j;
+            factorial *= k;
+            n_k *= n;
+
+            let (_, inverse, _) = factorial.extended_gcd(&n_j);
+            let term = (&t2 * &n_k * imod(&inverse, &n_j)) % &n_j;
+            t1 = mod_sub(&t1, &term, &n_j);
+        }
+
+        i = t1 % &n_j;
+        n_j = n_next;
+    }
+
+    i
+}
+
+impl PrivateKey for DamgardJurikPrivate {
+    #[inline]
+    fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.decrypt_block(&DamgardJurikCiphertext::from_bytes(message)?)
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::DamgardJurik
+    }
+}
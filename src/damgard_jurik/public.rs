@@ -0,0 +1,154 @@
+use ibig::UBig;
+use rand::{CryptoRng, RngCore};
+
+use super::DamgardJurikCiphertext;
+use crate::homomorphic::Homomorphic;
+use crate::result::{Algorithm, Error};
+use crate::utils::{CiphertextSpace, PowMod};
+use crate::{keypair::PublicKey, result::Result};
+
+/// Public key for the Damgård–Jurik cryptosystem.
+///
+/// Plaintexts are taken modulo `n ^ s` and ciphertexts modulo `n ^ (s + 1)`, so the
+/// ciphertext expansion shrinks to `(s + 1) / s`. With `s = 1` the scheme is Paillier.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DamgardJurikPublic {
+    /// Public modulus `n`, the product of two primes of equal length.
+    pub divisor: UBig,
+
+    /// The degree `s` of the plaintext space `n ^ s`.
+    pub degree: usize,
+}
+
+impl DamgardJurikPublic {
+    /// Checks that the key is plausible before it is used.
+    ///
+    /// The modulus must be odd, at least `MIN_MODULUS_BITS` wide and free of small factors,
+    /// and the degree must be at least 1.
+    #[inline]
+    pub fn validate(&self) -> Result<()> {
+        self.space().validate()
+    }
+
+    /// Returns `n ^ s`, the size of the plaintext space.
+    #[inline]
+    pub fn plaintext_modulus(&self) -> UBig {
+        self.space().plaintext_modulus()
+    }
+
+    /// Returns `n ^ (s + 1)`, the modulus ciphertexts live in.
+    #[inline]
+    pub fn ciphertext_modulus(&self) -> UBig {
+        self.space().ciphertext_modulus()
+    }
+
+    /// Returns the ciphertext space of the key.
+    #[inline]
+    pub(crate) fn space(&self) -> CiphertextSpace<'_> {
+        CiphertextSpace {
+            algorithm: Algorithm::DamgardJurik,
+            divisor: &self.divisor,
+            degree: self.degree,
+        }
+    }
+
+    /// Encrypts a single block into a typed ciphertext.
+    #[inline]
+    pub fn encrypt_block(&self, bytes: &[u8]) -> Result<DamgardJurikCiphertext> {
+        self.encrypt_block_with_rng(bytes, &mut rand::thread_rng())
+    }
+
+    /// Encrypts a single block into a typed ciphertext, drawing the randomness from `rng`.
+    pub fn encrypt_block_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<DamgardJurikCiphertext> {
+        let message = UBig::from_le_bytes(bytes);
+        if message >= self.plaintext_modulus() {
+            return Err(Error::MessageTooLarge {
+                algorithm: Algorithm::DamgardJurik,
+                max: self.max_message_size(),
+                actual: bytes.len(),
+            });
+        }
+
+        let modulus = self.ciphertext_modulus();
+        let shifted = (&self.divisor + 1u8).powmod(message, &modulus);
+        Ok(DamgardJurikCiphertext(
+            (shifted * self.space().random_mask(rng)) % modulus,
+        ))
+    }
+}
+
+/// Damgård–Jurik is additively homomorphic: [`Homomorphic::mul`] adds the plaintexts modulo
+/// `n ^ s` and [`Homomorphic::pow`] multiplies the plaintext by a scalar.
+impl Homomorphic for DamgardJurikPublic {
+    type Ciphertext = DamgardJurikCiphertext;
+
+    #[inline]
+    fn mul(
+        &self,
+        a: &DamgardJurikCiphertext,
+        b: &DamgardJurikCiphertext,
+    ) -> Result<DamgardJurikCiphertext> {
+        self.space().mul(&a.0, &b.0).map(DamgardJurikCiphertext)
+    }
+
+    #[inline]
+    fn pow(
+        &self,
+        ciphertext: &DamgardJurikCiphertext,
+        exponent: &UBig,
+    ) -> Result<DamgardJurikCiphertext> {
+        self.space()
+            .pow(&ciphertext.0, exponent)
+            .map(DamgardJurikCiphertext)
+    }
+
+    /// Multiplies the ciphertext with a fresh `r ^ (n ^ s)`, an encryption of zero.
+    #[inline]
+    fn rerandomize_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        ciphertext: &DamgardJurikCiphertext,
+        rng: &mut R,
+    ) -> Result<DamgardJurikCiphertext> {
+        self.space()
+            .rerandomize(&ciphertext.0, rng)
+            .map(DamgardJurikCiphertext)
+    }
+}
+
+impl PublicKey for DamgardJurikPublic {
+    #[inline]
+    fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        self.encrypt_block(bytes).map(|c| c.to_bytes())
+    }
+
+    #[inline]
+    fn encrypt_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        self.encrypt_block_with_rng(bytes, rng)
+            .map(|c| c.to_bytes())
+    }
+
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.divisor.bit_len()
+    }
+
+    /// Plaintexts are taken modulo `n ^ s` rather than `n`.
+    #[inline]
+    fn max_message_size(&self) -> usize {
+        self.plaintext_modulus().bit_len().saturating_sub(1) / 8
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::DamgardJurik
+    }
+}
@@ -0,0 +1,89 @@
+use ibig::UBig;
+use rand::{rngs::StdRng, SeedableRng};
+
+use super::{DamgardJurikCiphertext, DamgardJurikKeyPair};
+use crate::keypair::{PrivateKey, PublicKey};
+use crate::result::Error;
+use crate::Homomorphic;
+
+const MESSAGE: [u8; 445] = *b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.";
+
+#[test]
+fn test_encrypt_decrypt() {
+    for degree in 1..=3 {
+        let key = DamgardJurikKeyPair::new(64, degree, 5).unwrap();
+        assert!(key.max_message_size() >= degree * 15);
+
+        let encrypted = key.encrypt_chunked(&MESSAGE).unwrap();
+        let decrypted = key.decrypt_chunked(&encrypted).unwrap();
+        assert_eq!(MESSAGE, decrypted.as_slice());
+    }
+}
+
+#[test]
+fn test_new_rejects_bad_parameters() {
+    assert!(matches!(
+        DamgardJurikKeyPair::new(64, 0, 5),
+        Err(Error::KeyGeneration { .. })
+    ));
+    assert!(matches!(
+        DamgardJurikKeyPair::new(8, 1, 5),
+        Err(Error::KeyGeneration { .. })
+    ));
+}
+
+#[test]
+fn test_homomorphic_operations() {
+    let key = DamgardJurikKeyPair::new(64, 3, 5).unwrap();
+    let decrypt =
+        |c: &DamgardJurikCiphertext| UBig::from_le_bytes(&key.private.decrypt_block(c).unwrap());
+
+    // Both values are far larger than n, which plain Paillier could not hold.
+    let a = UBig::from(3u8) << 200;
+    let b = UBig::from(5u8) << 180;
+    let ca = key.public.encrypt_block(&a.to_le_bytes()).unwrap();
+    let cb = key.public.encrypt_block(&b.to_le_bytes()).unwrap();
+
+    let sum = key.public.mul(&ca, &cb).unwrap();
+    assert_eq!(decrypt(&sum), &a + &b);
+
+    let scaled = key.public.pow(&cb, &UBig::from(7u8)).unwrap();
+    assert_eq!(decrypt(&scaled), &b * 7u8);
+
+    let fresh = key.public.rerandomize(&ca).unwrap();
+    assert_ne!(fresh, ca);
+    assert_eq!(decrypt(&fresh), a);
+}
+
+#[test]
+fn test_threshold_decryption() {
+    let mut rng = StdRng::seed_from_u64(13);
+    let key = DamgardJurikKeyPair::new_with_rng(64, 2, 5, &mut rng).unwrap();
+    let shares = key.private.share_with_rng(3, 5, &mut rng).unwrap();
+
+    let ciphertext = key
+        .public
+        .encrypt_block_with_rng(b"Lorem", &mut rng)
+        .unwrap();
+    let partial = |i: usize| shares[i].decrypt_partial(&ciphertext).unwrap();
+
+    // Any three of the five shares decrypt, in any order.
+    for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+        let partials: Vec<_> = subset.iter().map(|&i| partial(i)).collect();
+        assert_eq!(key.public.combine_partials(&partials).unwrap(), b"Lorem");
+    }
+
+    assert!(matches!(
+        key.public.combine_partials(&[partial(0), partial(1)]),
+        Err(Error::MalformedInput { .. })
+    ));
+    assert!(matches!(
+        key.public
+            .combine_partials(&[partial(0), partial(0), partial(1)]),
+        Err(Error::MalformedInput { .. })
+    ));
+    assert!(matches!(
+        key.private.share(6, 5),
+        Err(Error::KeyGeneration { .. })
+    ));
+}
@@ -0,0 +1,232 @@
+use std::collections::HashSet;
+
+use ibig::{ops::UnsignedAbs, ubig, IBig, UBig};
+use rand::{CryptoRng, Rng, RngCore};
+
+use super::private::discrete_log;
+use super::{DamgardJurikCiphertext, DamgardJurikPrivate, DamgardJurikPublic};
+use crate::result::{Algorithm, Error, Result};
+use crate::secret::SecretUBig;
+use crate::utils::{ct_powmod, imod, PowMod};
+
+/// One party's share of the decryption exponent `d` of a Damgård–Jurik key.
+///
+/// Any `threshold` of the `parties` shares decrypt together, while fewer learn nothing
+/// about `d` from the shares themselves.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DamgardJurikKeyShare {
+    /// The index `i` of the share, in `[1, parties]`.
+    pub index: usize,
+
+    /// The number of shares needed to decrypt.
+    pub threshold: usize,
+
+    /// The number of shares `d` was split into.
+    pub parties: usize,
+
+    /// The public key the share decrypts for.
+    pub public: DamgardJurikPublic,
+
+    /// The share `s_i = f(i)` of the secret polynomial with `f(0) = d`.
+    pub share: SecretUBig,
+}
+
+/// A ciphertext raised to `2 * Δ * s_i` by the holder of share `i`, where `Δ = parties!`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DamgardJurikPartialDecryption {
+    /// The index of the share that produced it.
+    pub index: usize,
+
+    /// The number of shares needed to decrypt.
+    pub threshold: usize,
+
+    /// The number of shares `d` was split into.
+    pub parties: usize,
+
+    /// The value `c ^ (2 * Δ * s_i) mod n ^ (s + 1)`.
+    pub value: UBig,
+}
+
+impl DamgardJurikPrivate {
+    /// Splits the decryption exponent into `parties` shares, any `threshold` of which
+    /// decrypt together.
+    #[inline]
+    pub fn share(&self, threshold: usize, parties: usize) -> Result<Vec<DamgardJurikKeyShare>> {
+        self.share_with_rng(threshold, parties, &mut rand::thread_rng())
+    }
+
+    /// Like [`DamgardJurikPrivate::share`], but draws the polynomial from `rng`.
+    ///
+    /// `d` is shared with a random polynomial of degree `threshold - 1` over
+    /// `Z / (n ^ s * λ)`, whose order every ciphertext exponent can be reduced by.
+    /// Fails with [`Error::KeyGeneration`] if `threshold` is zero or above `parties`,
+    /// or if there are so many parties that `parties!` shares a factor with `n`.
+    pub fn share_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        threshold: usize,
+        parties: usize,
+        rng: &mut R,
+    ) -> Result<Vec<DamgardJurikKeyShare>> {
+        let invalid = |reason| {
+            Err(Error::KeyGeneration {
+                algorithm: Algorithm::DamgardJurik,
+                reason,
+            })
+        };
+
+        if threshold == 0 || threshold > parties {
+            return invalid("threshold must lie between 1 and the number of parties");
+        }
+
+        let (p, q) = (self.prime_1.expose(), self.prime_2.expose());
+        if UBig::from(parties) >= *p.min(q) {
+            return invalid("too many parties for the modulus");
+        }
+
+        let (p1, q1) = (p - 1u8, q - 1u8);
+        let lambda = &p1 * &q1 / p1.gcd(&q1);
+        let order = (p * q).pow(self.degree) * lambda;
+
+        let mut coefficients = vec![self.exponent.expose().clone()];
+        coefficients.extend((1..threshold).map(|_| rng.gen_range(ubig!(0)..order.clone())));
+
+        let public = DamgardJurikPublic {
+            divisor: p * q,
+            degree: self.degree,
+        };
+
+        Ok((1..=parties)
+            .map(|index| {
+                let x = UBig::from(index);
+                let share = coefficients
+                    .iter()
+                    .rev()
+                    .fold(ubig!(0), |acc, a| (acc * &x + a) % &order);
+
+                DamgardJurikKeyShare {
+                    index,
+                    threshold,
+                    parties,
+                    public: public.clone(),
+                    share: share.into(),
+                }
+            })
+            .collect())
+    }
+}
+
+impl DamgardJurikKeyShare {
+    /// Computes this party's contribution to the decryption of `ciphertext`.
+    pub fn decrypt_partial(
+        &self,
+        ciphertext: &DamgardJurikCiphertext,
+    ) -> Result<DamgardJurikPartialDecryption> {
+        let modulus = self.public.ciphertext_modulus();
+        self.public.space().check_ciphertext(&ciphertext.0)?;
+
+        if ciphertext.0.gcd(&self.public.divisor) != ubig!(1) {
+            return Err(Error::OutOfRange {
+                algorithm: Algorithm::DamgardJurik,
+                reason: "ciphertext is not invertible",
+            });
+        }
+
+        // The share is secret and goes through the ladder, the public 2 * Δ does not.
+        let power = ct_powmod(&ciphertext.0, self.share.expose(), &modulus);
+        Ok(DamgardJurikPartialDecryption {
+            index: self.index,
+            threshold: self.threshold,
+            parties: self.parties,
+            value: power.powmod(factorial(self.parties) * 2u8, &modulus),
+        })
+    }
+}
+
+impl DamgardJurikPublic {
+    /// Combines the partial decryptions of one ciphertext into its plaintext.
+    ///
+    /// The first `threshold` partial decryptions are combined with the integer Lagrange
+    /// coefficients `λ_i = Δ * Π j / (j - i)` into `c ^ (4 * Δ² * d)`, which is
+    /// `(1 + n) ^ (4 * Δ² * m)`, and the factor `4 * Δ²` is divided out of the exponent.
+    /// Fails with [`Error::MalformedInput`] if there are fewer than `threshold` of them,
+    /// or if they disagree on the sharing or repeat an index.
+    pub fn combine_partials(&self, partials: &[DamgardJurikPartialDecryption]) -> Result<Vec<u8>> {
+        let malformed = |reason| {
+            Err(Error::MalformedInput {
+                reason,
+                chunk: None,
+            })
+        };
+
+        let Some(first) = partials.first() else {
+            return malformed("no partial decryptions");
+        };
+        let (threshold, parties) = (first.threshold, first.parties);
+        if partials.len() < threshold {
+            return malformed("not enough partial decryptions");
+        }
+
+        let partials = &partials[..threshold];
+        let mut indices = HashSet::new();
+        for partial in partials {
+            if (partial.threshold, partial.parties) != (threshold, parties) {
+                return malformed("partial decryptions come from different sharings");
+            }
+
+            if partial.index == 0 || partial.index > parties || !indices.insert(partial.index) {
+                return malformed("partial decryption has an invalid or repeated index");
+            }
+        }
+
+        let modulus = self.ciphertext_modulus();
+        let delta = factorial(parties);
+
+        let mut combined = ubig!(1);
+        for partial in partials {
+            self.space().check_ciphertext(&partial.value)?;
+
+            let i = IBig::from(partial.index);
+            let (numerator, denominator) = partials
+                .iter()
+                .filter(|other| other.index != partial.index)
+                .map(|other| IBig::from(other.index))
+                .fold(
+                    (IBig::from(delta.clone()), IBig::from(1u8)),
+                    |(num, den), j| (num * &j, den * (&j - &i)),
+                );
+
+            let lagrange = numerator / denominator;
+            let base = match lagrange < IBig::from(0u8) {
+                true => invert(&partial.value, &modulus)?,
+                false => partial.value.clone(),
+            };
+            let exponent = lagrange.unsigned_abs() * 2u8;
+            combined = combined * base.powmod(exponent, &modulus) % &modulus;
+        }
+
+        let plaintext_modulus = self.plaintext_modulus();
+        let scaled = discrete_log(&combined, &self.divisor, self.degree);
+        let scale = invert(&(delta.pow(2) * 4u8), &plaintext_modulus)?;
+
+        Ok(((scaled * scale) % plaintext_modulus).to_le_bytes())
+    }
+}
+
+/// Returns `n!`.
+fn factorial(n: usize) -> UBig {
+    (1..=n).fold(ubig!(1), |acc, k| acc * k)
+}
+
+/// Returns the inverse of `value` modulo `modulus`.
+fn invert(value: &UBig, modulus: &UBig) -> Result<UBig> {
+    let (gcd, inverse, _) = value.extended_gcd(modulus);
+    match gcd == ubig!(1) {
+        true => Ok(imod(&inverse, modulus)),
+        false => Err(Error::OutOfRange {
+            algorithm: Algorithm::DamgardJurik,
+            reason: "value is not invertible",
+        }),
+    }
+}
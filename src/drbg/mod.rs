@@ -0,0 +1,190 @@
+//! Deterministic random bit generators following NIST SP 800-90A.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use digest::{core_api::BlockSizeUser, Digest};
+use hmac::{Mac, SimpleHmac};
+use ibig::UBig;
+use rand::{CryptoRng, RngCore};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::result::{Error, Result};
+use crate::utils::{bits_to_int, to_fixed_be_bytes};
+
+/// Number of requests after which the generator refuses to produce output until it is reseeded.
+pub const RESEED_INTERVAL: u64 = 1 << 48;
+
+/// Largest number of bytes a single call to [`HmacDrbg::generate`] may return.
+pub const MAX_BYTES_PER_REQUEST: usize = 1 << 16;
+
+/// HMAC-DRBG as specified in NIST SP 800-90A, without prediction resistance.
+///
+/// The generator is fully determined by the entropy, nonce and personalization
+/// string it is instantiated with, which makes it suitable for known-answer tests
+/// and for reproducible key generation through the `*_with_rng` methods.
+///
+/// The internal state is wiped when the generator is dropped.
+pub struct HmacDrbg<D = Sha256> {
+    key: Zeroizing<Vec<u8>>,
+    value: Zeroizing<Vec<u8>>,
+    reseed_counter: u64,
+    digest: PhantomData<D>,
+}
+
+impl<D: Digest + BlockSizeUser> HmacDrbg<D> {
+    /// Instantiates the generator from the entropy input, a nonce and an optional
+    /// personalization string.
+    pub fn new(entropy: &[u8], nonce: &[u8], personalization: &[u8]) -> Self {
+        let length = <D as Digest>::output_size();
+        let mut drbg = Self {
+            key: Zeroizing::new(vec![0x00; length]),
+            value: Zeroizing::new(vec![0x01; length]),
+            reseed_counter: 1,
+            digest: PhantomData,
+        };
+
+        drbg.update(&[entropy, nonce, personalization]);
+        drbg
+    }
+
+    /// Mixes fresh entropy and optional additional input into the state
+    /// and resets the reseed counter.
+    pub fn reseed(&mut self, entropy: &[u8], additional_input: &[u8]) {
+        self.update(&[entropy, additional_input]);
+        self.reseed_counter = 1;
+    }
+
+    /// Fills `output` with pseudorandom bytes, mixing in optional additional input.
+    ///
+    /// Fails with [`Error::ReseedRequired`] once [`RESEED_INTERVAL`] requests have been
+    /// served, and with [`Error::MalformedInput`] if more than [`MAX_BYTES_PER_REQUEST`]
+    /// bytes are requested at once.
+    pub fn generate(&mut self, output: &mut [u8], additional_input: &[u8]) -> Result<()> {
+        if output.len() > MAX_BYTES_PER_REQUEST {
+            return Err(Error::MalformedInput {
+                reason: "too many bytes requested at once",
+                chunk: None,
+            });
+        }
+
+        if self.reseed_counter > RESEED_INTERVAL {
+            return Err(Error::ReseedRequired);
+        }
+
+        if !additional_input.is_empty() {
+            self.update(&[additional_input]);
+        }
+
+        for block in output.chunks_mut(self.value.len()) {
+            self.value = self.mac(&[&self.value]);
+            block.copy_from_slice(&self.value[..block.len()]);
+        }
+
+        self.update(&[additional_input]);
+        self.reseed_counter += 1;
+
+        Ok(())
+    }
+
+    /// The HMAC_DRBG_Update function, with `provided` being the concatenation of its parts.
+    fn update(&mut self, provided: &[&[u8]]) {
+        self.update_round(0x00, provided);
+
+        if provided.iter().any(|part| !part.is_empty()) {
+            self.update_round(0x01, provided);
+        }
+    }
+
+    /// Computes `K = HMAC(K, V || separator || provided)` followed by `V = HMAC(K, V)`.
+    fn update_round(&mut self, separator: u8, provided: &[&[u8]]) {
+        let separator = [separator];
+        let mut input: Vec<&[u8]> = vec![&self.value, &separator];
+        input.extend(provided);
+
+        self.key = self.mac(&input);
+        self.value = self.mac(&[&self.value]);
+    }
+
+    /// Computes the HMAC of the concatenated `input` under the current key.
+    fn mac(&self, input: &[&[u8]]) -> Zeroizing<Vec<u8>> {
+        let mut mac = <SimpleHmac<D> as Mac>::new_from_slice(&self.key)
+            .expect("HMAC accepts keys of any length");
+
+        for part in input {
+            mac.update(part);
+        }
+
+        Zeroizing::new(mac.finalize().into_bytes().to_vec())
+    }
+}
+
+impl<D: Digest + BlockSizeUser> RngCore for HmacDrbg<D> {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    /// Panics if the generator has to be reseeded; use `try_fill_bytes` to handle that case.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(error) = self.try_fill_bytes(dest) {
+            panic!("{error}");
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> std::result::Result<(), rand::Error> {
+        for chunk in dest.chunks_mut(MAX_BYTES_PER_REQUEST) {
+            self.generate(chunk, &[]).map_err(rand::Error::new)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<D: Digest + BlockSizeUser> CryptoRng for HmacDrbg<D> {}
+
+/// The internal state is never printed.
+impl<D> fmt::Debug for HmacDrbg<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HmacDrbg").finish_non_exhaustive()
+    }
+}
+
+/// Derives a nonce in `[1, order)` from a private key and a message hash as in RFC 6979.
+///
+/// Candidates are drawn from an HMAC-DRBG seeded with the key and the reduced hash
+/// until one also satisfies `accept`, so rejected candidates are replaced deterministically.
+pub(crate) fn rfc6979_nonce<D, F>(key: &UBig, hash: &[u8], order: &UBig, accept: F) -> UBig
+where
+    D: Digest + BlockSizeUser,
+    F: Fn(&UBig) -> bool,
+{
+    let bit_length = order.bit_len();
+    let length = bit_length.div_ceil(8);
+
+    let reduced = bits_to_int(hash, bit_length) % order;
+    let mut seed = Zeroizing::new(to_fixed_be_bytes(key, length));
+    seed.extend(to_fixed_be_bytes(&reduced, length));
+
+    let mut drbg = HmacDrbg::<D>::new(&seed, &[], &[]);
+    let mut candidate = Zeroizing::new(vec![0; length]);
+    loop {
+        drbg.fill_bytes(&mut candidate);
+
+        let nonce = bits_to_int(&candidate, bit_length);
+        if nonce > UBig::from(0u8) && nonce < *order && accept(&nonce) {
+            break nonce;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,59 @@
+use ibig::UBig;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+use super::{rfc6979_nonce, HmacDrbg};
+use crate::rsa::RsaKeyPair;
+
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// First HMAC_DRBG SHA-256 vector of the NIST CAVP set without prediction
+/// resistance, personalization string or additional input.
+#[test]
+fn test_hmac_drbg_known_answer() {
+    let entropy = hex("ca851911349384bffe89de1cbdc46e6831e44d34a4fb935ee285dd14b71a7488");
+    let nonce = hex("659ba96c601dc69fc902940805ec0ca8");
+    let expected = hex(concat!(
+        "e528e9abf2dece54d47c7e75e5fe302149f817ea9fb4bee6f4199697d04d5b89",
+        "d54fbb978a15b5c443c9ec21036d2460b6f73ebad0dc2aba6e624abf07745bc1",
+        "07694bb7547bb0995f70de25d6b29e2d3011bb19d27676c07162c8b5ccde0668",
+        "961df86803482cb37ed6d5c0bb8d50cf1f50d476aa0458bdaba806f48be9dcb8",
+    ));
+
+    let mut drbg = HmacDrbg::<Sha256>::new(&entropy, &nonce, &[]);
+    let mut output = vec![0; expected.len()];
+    drbg.generate(&mut output, &[]).unwrap();
+    drbg.generate(&mut output, &[]).unwrap();
+
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn test_hmac_drbg_drives_key_generation() {
+    let new_drbg = || HmacDrbg::<Sha256>::new(b"entropy input", b"nonce", b"encipher");
+
+    let a = RsaKeyPair::new_with_rng(128, 5, &mut new_drbg()).unwrap();
+    let b = RsaKeyPair::new_with_rng(128, 5, &mut new_drbg()).unwrap();
+    assert_eq!(a.public.divisor, b.public.divisor);
+
+    let mut reseeded = new_drbg();
+    reseeded.reseed(b"fresh entropy", &[]);
+    assert_ne!(reseeded.next_u64(), new_drbg().next_u64());
+}
+
+/// RFC 6979, appendix A.1.2: DSA with a 163-bit `q`, message "sample" and SHA-256.
+#[test]
+fn test_rfc6979_nonce_known_answer() {
+    let parse = |s| UBig::from_str_radix(s, 16).unwrap();
+    let order = parse("4000000000000000000020108A2E0CC0D99F8A5EF");
+    let key = parse("09A4D6792295A7F730FC3F2B49CBC0F62E862272F");
+    let hash = Sha256::digest(b"sample");
+
+    let nonce = rfc6979_nonce::<Sha256, _>(&key, &hash, &order, |_| true);
+    assert_eq!(nonce, parse("23AF4074C90A02B3FE61D286D5C87F425E6BDD81B"));
+}
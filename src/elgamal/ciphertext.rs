@@ -0,0 +1,40 @@
+use ibig::UBig;
+
+use crate::result::{Error, Result};
+use crate::utils::{marshal_bytes, tag_bytes, unmarshal_bytes, untag_bytes, TypeTag};
+
+/// A single block encrypted with an ElGamal public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElGamalCiphertext {
+    /// The ephemeral key `g ^ r mod p`.
+    pub c1: UBig,
+
+    /// The masked message `m * beta ^ r mod p`.
+    pub c2: UBig,
+}
+
+impl ElGamalCiphertext {
+    /// Encodes both components as marshalled little-endian bytes behind the type tag.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let components = marshal_bytes(&vec![self.c1.to_le_bytes(), self.c2.to_le_bytes()]);
+        tag_bytes(TypeTag::ElGamalCiphertext, &components)
+    }
+
+    /// Decodes a ciphertext produced by [`ElGamalCiphertext::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let [c1, c2] = <[Vec<u8>; 2]>::try_from(unmarshal_bytes(untag_bytes(
+            TypeTag::ElGamalCiphertext,
+            bytes,
+        )?)?)
+        .map_err(|_| Error::MalformedInput {
+            reason: "expected exactly two components",
+            chunk: None,
+        })?;
+
+        Ok(Self {
+            c1: UBig::from_le_bytes(&c1),
+            c2: UBig::from_le_bytes(&c2),
+        })
+    }
+}
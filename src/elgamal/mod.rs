@@ -1,10 +1,50 @@
+mod ciphertext;
+mod groups;
 mod pair;
+mod params;
 mod private;
 mod public;
+mod signature;
 
+use ibig::{ubig, UBig};
+
+use crate::result::{Algorithm, Error, Result};
+use crate::utils::PowMod;
+
+pub use ciphertext::ElGamalCiphertext;
+pub use groups::NamedGroup;
 pub use pair::ElGamalKeyPair;
-pub use private::ElGamalPrivate;
+pub use params::ElGamalParams;
+pub use private::{ElGamalPrivate, MAX_EXPONENT_BOUND};
 pub use public::ElGamalPublic;
+pub use signature::ElGamalSignature;
+
+/// Ensures that `value` lies in `[1, p - 1]`, the multiplicative group modulo `prime`.
+fn check_element(value: &UBig, prime: &UBig, reason: &'static str) -> Result<()> {
+    match *value >= ubig!(1) && value < prime {
+        true => Ok(()),
+        false => Err(Error::OutOfRange {
+            algorithm: Algorithm::ElGamal,
+            reason,
+        }),
+    }
+}
+
+/// Checks whether `value` lies in the subgroup of prime order `order` modulo `prime`.
+pub(crate) fn in_subgroup(value: &UBig, prime: &UBig, order: &UBig) -> bool {
+    *value >= ubig!(1) && value < prime && value.powmod(order.clone(), prime) == ubig!(1)
+}
+
+/// Ensures that `value` lies in the subgroup of prime order `order` modulo `prime`.
+fn check_subgroup(value: &UBig, prime: &UBig, order: &UBig, reason: &'static str) -> Result<()> {
+    match in_subgroup(value, prime, order) {
+        true => Ok(()),
+        false => Err(Error::OutOfRange {
+            algorithm: Algorithm::ElGamal,
+            reason,
+        }),
+    }
+}
 
 #[cfg(test)]
 mod tests;
@@ -1,9 +1,10 @@
 use ibig::ubig;
-use ibig_ext::{powmod::PowMod, prime_gen::gen_sized_prime};
-use rand::Rng;
+use rand::{CryptoRng, Rng, RngCore};
 
-use super::{ElGamalPrivate, ElGamalPublic};
-use crate::{result::Result, PrivateKey, PublicKey};
+use super::{ElGamalParams, ElGamalPrivate, ElGamalPublic};
+use crate::result::{Algorithm, Result};
+use crate::utils::PowMod;
+use crate::{PrivateKey, PublicKey};
 use crate::{Signer, Verifier};
 
 /// A key pair for the ElGamal cryptosystem.
@@ -20,32 +21,55 @@ pub struct ElGamalKeyPair {
 impl ElGamalKeyPair {
     /// Generates a new ElGamal key pair with the specified bit length and persistence level.
     ///
+    /// Fresh domain parameters are generated for the pair; prefer [`ElGamalKeyPair::from_params`]
+    /// when many keys are needed.
+    ///
     /// * `bit_length`: The desired bit length for the keys in the pair.
     /// * `persistence`: The number of iterations for checking numbers for primality.
     ///
-    /// Returns a newly generated `ElGamalKeyPair` instance.
-    pub fn new(bit_length: usize, persistence: usize) -> Self {
-        let mut rng = rand::thread_rng();
+    /// Returns a newly generated `ElGamalKeyPair` instance, or
+    /// [`Error::KeyGeneration`](crate::result::Error::KeyGeneration) if `bit_length` is too small.
+    #[inline]
+    pub fn new(bit_length: usize, persistence: usize) -> Result<Self> {
+        Self::new_with_rng(bit_length, persistence, &mut rand::thread_rng())
+    }
+
+    /// Generates a new ElGamal key pair, drawing all randomness from `rng`.
+    ///
+    /// The same seeded generator always yields the same key pair.
+    pub fn new_with_rng<R: RngCore + CryptoRng>(
+        bit_length: usize,
+        persistence: usize,
+        rng: &mut R,
+    ) -> Result<Self> {
+        let params = ElGamalParams::generate_with_rng(bit_length, persistence, rng)?;
+        Ok(Self::from_params_with_rng(params, rng))
+    }
 
-        let prime = gen_sized_prime(bit_length, persistence);
-        let alpha = loop {
-            let i = rng.gen_range(ubig!(3)..&prime - 1);
-            if i.powmod(&prime - 1, &prime) == ubig!(1) {
-                break i;
-            }
-        };
+    /// Generates a key pair in an existing group, which only takes a single exponentiation.
+    #[inline]
+    pub fn from_params(params: ElGamalParams) -> Self {
+        Self::from_params_with_rng(params, &mut rand::thread_rng())
+    }
 
-        let key = rng.gen_range(ubig!(1)..&prime - 1);
-        let beta = alpha.powmod(key.clone(), &prime);
+    /// Generates a key pair in an existing group, drawing the private key from `rng`.
+    pub fn from_params_with_rng<R: RngCore + CryptoRng>(
+        params: ElGamalParams,
+        rng: &mut R,
+    ) -> Self {
+        let key = rng.gen_range(ubig!(1)..params.q.clone());
+        let beta = params.g.powmod(key.clone(), &params.p);
 
         Self {
             public: ElGamalPublic {
-                prime: prime.clone(),
-                alpha: alpha.clone(),
+                params: params.clone(),
                 beta,
             },
 
-            private: ElGamalPrivate { alpha, prime, key },
+            private: ElGamalPrivate {
+                params,
+                key: key.into(),
+            },
         }
     }
 }
@@ -55,6 +79,11 @@ impl PrivateKey for ElGamalKeyPair {
     fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
         self.private.decrypt(message)
     }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::ElGamal
+    }
 }
 
 impl PublicKey for ElGamalKeyPair {
@@ -62,21 +91,48 @@ impl PublicKey for ElGamalKeyPair {
     fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
         self.public.encrypt(bytes)
     }
+
+    #[inline]
+    fn encrypt_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        self.public.encrypt_with_rng(bytes, rng)
+    }
+
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.public.bit_length()
+    }
+
+    #[inline]
+    fn max_message_size(&self) -> usize {
+        self.public.max_message_size()
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::ElGamal
+    }
 }
 
 impl Verifier for ElGamalKeyPair {
-    fn verify(
-        &self,
-        expected: &[u8],
-        signed_data: &[u8],
-        hashf: fn(&[u8]) -> Vec<u8>,
-    ) -> Result<bool> {
-        self.public.verify(expected, signed_data, hashf)
+    fn verify_hash(&self, hash: &[u8], signed_data: &[u8]) -> Result<bool> {
+        self.public.verify_hash(hash, signed_data)
     }
 }
 
 impl Signer for ElGamalKeyPair {
-    fn sign(&self, data: &[u8], hashf: fn(&[u8]) -> Vec<u8>) -> Result<Vec<u8>> {
-        self.private.sign(data, hashf)
+    fn sign_hash(&self, hash: &[u8]) -> Result<Vec<u8>> {
+        self.private.sign_hash(hash)
+    }
+
+    fn sign_hash_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        hash: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        self.private.sign_hash_with_rng(hash, rng)
     }
 }
@@ -0,0 +1,201 @@
+use ibig::{ubig, UBig};
+use rand::{CryptoRng, Rng, RngCore};
+
+use super::{check_element, in_subgroup, NamedGroup};
+use crate::result::{Algorithm, Error, Result};
+use crate::utils::{
+    check_prime_bits, gen_prime, has_small_factor, is_probable_prime, PowMod, MIN_MODULUS_BITS,
+    VALIDATION_ROUNDS,
+};
+
+/// Domain parameters of an ElGamal group, shared by any number of key pairs.
+///
+/// Generating the parameters is by far the most expensive part of creating a key,
+/// so they are meant to be generated once and reused with [`super::ElGamalKeyPair::from_params`],
+/// or taken from one of the standardized groups in [`NamedGroup`].
+///
+/// Parameters of a named group are serialized as the name of the group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "ParamsRepr", into = "ParamsRepr"))]
+pub struct ElGamalParams {
+    /// Prime modulus of the group.
+    pub p: UBig,
+
+    /// Prime order of the subgroup generated by `g`, a divisor of `p - 1`.
+    pub q: UBig,
+
+    /// Generator of the subgroup of order `q`.
+    pub g: UBig,
+}
+
+impl ElGamalParams {
+    /// Returns the parameters of a standardized group.
+    #[inline]
+    pub fn named(group: NamedGroup) -> Self {
+        group.params()
+    }
+
+    /// Generates parameters over a safe prime `p = 2q + 1` of exactly `bit_length` bits.
+    ///
+    /// * `bit_length`: The desired bit length of the modulus.
+    /// * `persistence`: The number of iterations for checking numbers for primality.
+    ///
+    /// Fails with [`Error::KeyGeneration`] if `bit_length` is too small.
+    #[inline]
+    pub fn generate(bit_length: usize, persistence: usize) -> Result<Self> {
+        Self::generate_with_rng(bit_length, persistence, &mut rand::thread_rng())
+    }
+
+    /// Generates parameters like [`ElGamalParams::generate`], drawing all randomness from `rng`.
+    pub fn generate_with_rng<R: RngCore + CryptoRng>(
+        bit_length: usize,
+        persistence: usize,
+        rng: &mut R,
+    ) -> Result<Self> {
+        check_prime_bits(Algorithm::ElGamal, bit_length.saturating_sub(1))?;
+
+        let (p, q) = loop {
+            let q = gen_prime(bit_length - 1, persistence, rng, |q| {
+                !has_small_factor(&(q * 2u8 + 1u8))
+            });
+
+            let p = &q * 2u8 + 1u8;
+            if is_probable_prime(&p, persistence, rng) {
+                break (p, q);
+            }
+        };
+
+        // Squaring maps the whole group onto the subgroup of order q, and every
+        // element of that subgroup other than 1 generates it.
+        let g = loop {
+            let h = rng.gen_range(ubig!(2)..&p - 1);
+            let g = h.powmod(ubig!(2), &p);
+            if g != ubig!(1) {
+                break g;
+            }
+        };
+
+        Ok(Self { p, q, g })
+    }
+
+    /// Maps a message onto an element of the subgroup of quadratic residues.
+    ///
+    /// The message `m` becomes `m + 1` if that is a quadratic residue and `p - (m + 1)`
+    /// otherwise. As `p = 2q + 1` is a safe prime, exactly one of the two is a residue,
+    /// so every message below `q` has an encoding and ciphertexts reveal nothing
+    /// about it through their Legendre symbol.
+    pub fn encode(&self, bytes: &[u8]) -> Result<UBig> {
+        if self.p != &self.q * 2u8 + 1u8 {
+            return Err(Error::InvalidKey {
+                algorithm: Algorithm::ElGamal,
+                reason: "messages can only be encoded in safe-prime groups",
+            });
+        }
+
+        let shifted = UBig::from_le_bytes(bytes) + 1u8;
+        if shifted > self.q {
+            return Err(Error::MessageTooLarge {
+                algorithm: Algorithm::ElGamal,
+                max: self.max_message_size(),
+                actual: bytes.len(),
+            });
+        }
+
+        match in_subgroup(&shifted, &self.p, &self.q) {
+            true => Ok(shifted),
+            false => Ok(&self.p - shifted),
+        }
+    }
+
+    /// Recovers the message from an element produced by [`ElGamalParams::encode`].
+    pub fn decode(&self, element: &UBig) -> Result<Vec<u8>> {
+        check_element(element, &self.p, "element is not in the group")?;
+
+        let shifted = match *element <= self.q {
+            true => element.clone(),
+            false => &self.p - element,
+        };
+
+        Ok((shifted - 1u8).to_le_bytes())
+    }
+
+    /// Returns the number of bytes that [`ElGamalParams::encode`] always accepts.
+    #[inline]
+    pub fn max_message_size(&self) -> usize {
+        self.q.bit_len().saturating_sub(1) / 8
+    }
+
+    /// Checks that the parameters describe a usable group.
+    ///
+    /// The modulus must be at least `MIN_MODULUS_BITS` wide and pass a primality test,
+    /// the order must be a prime dividing `p - 1`, and `g` must be an element other
+    /// than `1` of the subgroup of that order.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |reason| {
+            Err(Error::InvalidKey {
+                algorithm: Algorithm::ElGamal,
+                reason,
+            })
+        };
+
+        if self.p.bit_len() < MIN_MODULUS_BITS {
+            return invalid("prime is too small");
+        }
+
+        if &self.p % 2u8 == 0u8 {
+            return invalid("prime is even");
+        }
+
+        if has_small_factor(&self.p)
+            || !is_probable_prime(&self.p, VALIDATION_ROUNDS, &mut rand::thread_rng())
+        {
+            return invalid("modulus is not prime");
+        }
+
+        if self.q <= ubig!(1)
+            || (&self.p - 1u8) % &self.q != ubig!(0)
+            || !is_probable_prime(&self.q, VALIDATION_ROUNDS, &mut rand::thread_rng())
+        {
+            return invalid("order is not a prime factor of p - 1");
+        }
+
+        if self.g == ubig!(1) || !in_subgroup(&self.g, &self.p, &self.q) {
+            return invalid("generator is not in the subgroup");
+        }
+
+        Ok(())
+    }
+}
+
+/// Serialized form of [`ElGamalParams`], which refers to named groups by name.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+enum ParamsRepr {
+    Named(NamedGroup),
+    Explicit { p: UBig, q: UBig, g: UBig },
+}
+
+#[cfg(feature = "serde")]
+impl From<ElGamalParams> for ParamsRepr {
+    fn from(params: ElGamalParams) -> Self {
+        match NamedGroup::identify(&params) {
+            Some(group) => ParamsRepr::Named(group),
+            None => {
+                let ElGamalParams { p, q, g } = params;
+                ParamsRepr::Explicit { p, q, g }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<ParamsRepr> for ElGamalParams {
+    fn from(repr: ParamsRepr) -> Self {
+        match repr {
+            ParamsRepr::Named(group) => group.params(),
+            ParamsRepr::Explicit { p, q, g } => ElGamalParams { p, q, g },
+        }
+    }
+}
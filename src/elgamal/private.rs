@@ -1,67 +1,202 @@
-use ibig::{ubig, IBig, UBig};
-use ibig_ext::powmod::PowMod;
-use rand::Rng;
+use std::collections::HashMap;
 
-use crate::result::Error;
-use crate::utils::{imod, imod_inverse, marshal_bytes, mod_sub, unmarshal_bytes};
+use ibig::{ubig, UBig};
+use rand::{CryptoRng, Rng, RngCore};
+use sha2::Sha256;
+
+use super::{check_subgroup, ElGamalCiphertext, ElGamalParams, ElGamalSignature};
+use crate::drbg::rfc6979_nonce;
+use crate::result::{Algorithm, Error};
+use crate::secret::SecretUBig;
+use crate::utils::{bits_to_int, ct_powmod, imod, mod_sub, PowMod};
 use crate::Signer;
 use crate::{keypair::PrivateKey, result::Result};
 
+/// Largest bound accepted by [`ElGamalPrivate::decrypt_exponent`].
+///
+/// The baby-step table then holds at most 65,537 group elements, each as wide as
+/// `p`: about 16 MiB for a 2048-bit group, plus the hash table's own overhead.
+pub const MAX_EXPONENT_BOUND: u64 = 1 << 32;
+
 /// Private key for the ElGamal cryptosystem.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ElGamalPrivate {
-    pub alpha: UBig,
-    pub prime: UBig,
-    pub key: UBig,
+    /// Domain parameters of the group the key lives in.
+    pub params: ElGamalParams,
+
+    /// Secret exponent in `[1, q)`.
+    pub key: SecretUBig,
 }
 
-impl PrivateKey for ElGamalPrivate {
+impl ElGamalPrivate {
+    /// Decrypts a single typed ciphertext block.
     #[inline]
-    fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
-        let cs = unmarshal_bytes(message);
-        let c1 = cs[0].as_slice();
-        let c2 = cs[1].as_slice();
+    pub fn decrypt_block(&self, ciphertext: &ElGamalCiphertext) -> Result<Vec<u8>> {
+        self.params.decode(&self.decrypt_element(ciphertext)?)
+    }
 
-        let c1 = UBig::from_le_bytes(c1);
-        let c2 = UBig::from_le_bytes(c2);
+    /// Decrypts a ciphertext into the group element it encrypts, without decoding it.
+    ///
+    /// Fails with [`Error::InvalidKey`] if the secret exponent does not lie in `[1, q)`.
+    pub fn decrypt_element(&self, ciphertext: &ElGamalCiphertext) -> Result<UBig> {
+        let ElGamalCiphertext { c1, c2 } = ciphertext;
 
-        let c1_inv = imod_inverse(&c1, &self.prime);
-        let c1_inv = c1_inv.powmod(self.key.clone(), &IBig::from(&self.prime));
-        let c1_inv: UBig = c1_inv.try_into().map_err(|_| Error::MathError)?;
+        let key = self.key.expose();
+        if *key == ubig!(0) || *key >= self.params.q {
+            return Err(Error::InvalidKey {
+                algorithm: Algorithm::ElGamal,
+                reason: "secret exponent is out of range",
+            });
+        }
 
-        let message = (c2 * c1_inv) % &self.prime;
-        let bytes = message.to_le_bytes();
+        check_subgroup(
+            c1,
+            &self.params.p,
+            &self.params.q,
+            "c1 is not in the subgroup",
+        )?;
+        check_subgroup(
+            c2,
+            &self.params.p,
+            &self.params.q,
+            "c2 is not in the subgroup",
+        )?;
 
-        Ok(bytes.to_vec())
+        // c1 ^ -key is computed as c1 ^ (q - key), which keeps the secret
+        // exponent inside the ladder and avoids a separate inversion.
+        let exponent = &self.params.q - key;
+        let c1_inv = ct_powmod(c1, &exponent, &self.params.p);
+
+        Ok((c2 * c1_inv) % &self.params.p)
     }
-}
 
-impl Signer for ElGamalPrivate {
-    fn sign(&self, data: &[u8], hashf: fn(&[u8]) -> Vec<u8>) -> Result<Vec<u8>> {
-        let mut rng = rand::thread_rng();
-        let modulo: UBig = &self.prime - 1;
-
-        let (r, r_inv) = loop {
-            let r = rng.gen_range(ubig!(2)..modulo.clone());
-            let (gcd, r_inv, _) = r.extended_gcd(&modulo);
-            if gcd == ubig!(1) {
-                let r_inv = imod(&r_inv, &modulo);
-                break (r, r_inv);
+    /// Decrypts an exponential ElGamal ciphertext into a value of at most `bound`.
+    ///
+    /// The discrete logarithm is found with baby-step giant-step, which takes
+    /// about `sqrt(bound)` time and stores `sqrt(bound)` elements of `p`'s width.
+    /// Fails with [`Error::OutOfRange`] if the plaintext is larger than `bound`,
+    /// or if `bound` exceeds [`MAX_EXPONENT_BOUND`].
+    pub fn decrypt_exponent(&self, ciphertext: &ElGamalCiphertext, bound: u64) -> Result<u64> {
+        if bound > MAX_EXPONENT_BOUND {
+            return Err(Error::OutOfRange {
+                algorithm: Algorithm::ElGamal,
+                reason: "discrete logarithm bound is too large",
+            });
+        }
+
+        let element = self.decrypt_element(ciphertext)?;
+        let ElGamalParams { p, q, g } = &self.params;
+
+        let steps = (bound as f64).sqrt() as u64 + 1;
+        let mut baby = HashMap::with_capacity(steps as usize);
+        let mut power = ubig!(1);
+        for j in 0..steps {
+            baby.entry(power.clone()).or_insert(j);
+            power = (power * g) % p;
+        }
+
+        // Each giant step multiplies by g ^ -steps, computed as g ^ (q - steps mod q).
+        let giant = g.powmod(q - UBig::from(steps) % q, p);
+        let mut gamma = element;
+        for i in 0..steps {
+            if let Some(j) = baby.get(&gamma) {
+                let value = i.checked_mul(steps).and_then(|v| v.checked_add(*j));
+                if let Some(value) = value.filter(|&value| value <= bound) {
+                    return Ok(value);
+                }
+            }
+
+            gamma = (gamma * &giant) % p;
+        }
+
+        Err(Error::OutOfRange {
+            algorithm: Algorithm::ElGamal,
+            reason: "plaintext exceeds the discrete logarithm bound",
+        })
+    }
+
+    /// Signs an already computed hash of the message.
+    ///
+    /// The nonce is derived from the key and the hash as in RFC 6979, so signing
+    /// never depends on the quality of a random number generator and the same
+    /// hash always yields the same signature.
+    pub fn create_signature(&self, hash: &[u8]) -> Result<ElGamalSignature> {
+        let r = rfc6979_nonce::<Sha256, _>(self.key.expose(), hash, &self.params.q, |r| {
+            nonce_inverse(r, &self.params.q).is_some()
+        });
+
+        self.sign_with_nonce(hash, r)
+    }
+
+    /// Signs an already computed hash of the message, drawing the nonce from `rng`.
+    pub fn create_signature_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        hash: &[u8],
+        rng: &mut R,
+    ) -> Result<ElGamalSignature> {
+        let r = loop {
+            let r = rng.gen_range(ubig!(1)..self.params.q.clone());
+            if nonce_inverse(&r, &self.params.q).is_some() {
+                break r;
             }
         };
 
-        let sigma = self.alpha.powmod(r, &self.prime);
+        self.sign_with_nonce(hash, r)
+    }
+
+    /// Computes the signature for a nonce `r` that is invertible modulo the group order.
+    fn sign_with_nonce(&self, hash: &[u8], r: UBig) -> Result<ElGamalSignature> {
+        let modulo = &self.params.q;
+        let r_inv = nonce_inverse(&r, modulo).expect("nonce is invertible");
+
+        let sigma = ct_powmod(&self.params.g, &r, &self.params.p);
         let delta = {
-            let a = UBig::from_le_bytes(&hashf(data));
-            let b = &self.key * &sigma;
+            let a = bits_to_int(hash, modulo.bit_len());
+            let b = self.key.expose() * &sigma;
 
-            (mod_sub(&a, &b, &modulo) * r_inv) % modulo
+            (mod_sub(&a, &b, modulo) * r_inv) % modulo
         };
 
-        let sigma_bytes = sigma.to_le_bytes();
-        let delta_bytes = delta.to_le_bytes();
+        Ok(ElGamalSignature { sigma, delta })
+    }
+}
 
-        Ok(marshal_bytes(&vec![sigma_bytes, delta_bytes]))
+/// Returns the inverse of the nonce modulo `modulo`, if there is one.
+fn nonce_inverse(r: &UBig, modulo: &UBig) -> Option<UBig> {
+    let (gcd, r_inv, _) = r.extended_gcd(modulo);
+    match gcd == ubig!(1) {
+        true => Some(imod(&r_inv, modulo)),
+        false => None,
+    }
+}
+
+impl PrivateKey for ElGamalPrivate {
+    #[inline]
+    fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.decrypt_block(&ElGamalCiphertext::from_bytes(message)?)
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::ElGamal
+    }
+}
+
+impl Signer for ElGamalPrivate {
+    #[inline]
+    fn sign_hash(&self, hash: &[u8]) -> Result<Vec<u8>> {
+        let signature = self.create_signature(hash)?;
+        Ok(signature.to_fixed_bytes(self.params.p.bit_len().div_ceil(8)))
+    }
+
+    #[inline]
+    fn sign_hash_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        hash: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        let signature = self.create_signature_with_rng(hash, rng)?;
+        Ok(signature.to_fixed_bytes(self.params.p.bit_len().div_ceil(8)))
     }
 }
@@ -1,66 +1,210 @@
 use ibig::{ubig, UBig};
-use ibig_ext::powmod::PowMod;
-use rand::Rng;
+use rand::{CryptoRng, Rng, RngCore};
 
-use crate::result::Error;
-use crate::utils::{marshal_bytes, unmarshal_bytes};
-use crate::Verifier;
+use super::{check_subgroup, in_subgroup, ElGamalCiphertext, ElGamalParams, ElGamalSignature};
+use crate::result::{Algorithm, Error};
+use crate::utils::{bits_to_int, PowMod};
 use crate::{keypair::PublicKey, result::Result};
+use crate::{Homomorphic, Verifier};
 
 /// Public key for the ElGamal cryptosystem.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ElGamalPublic {
-    pub prime: UBig,
-    pub alpha: UBig,
+    /// Domain parameters of the group the key lives in.
+    pub params: ElGamalParams,
+
+    /// Public value `g ^ key mod p`.
     pub beta: UBig,
 }
 
-impl PublicKey for ElGamalPublic {
-    #[inline]
-    fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
-        let message = UBig::from_le_bytes(bytes);
-        if message >= self.prime {
-            return Err(Error::SmallKey);
+impl ElGamalPublic {
+    /// Checks that the key is plausible before it is used.
+    ///
+    /// The domain parameters must pass [`ElGamalParams::validate`] and `beta`
+    /// must be an element other than `1` of the subgroup they describe.
+    pub fn validate(&self) -> Result<()> {
+        self.params.validate()?;
+
+        let ElGamalParams { p, q, .. } = &self.params;
+        if self.beta == ubig!(1) || !in_subgroup(&self.beta, p, q) {
+            return Err(Error::InvalidKey {
+                algorithm: Algorithm::ElGamal,
+                reason: "public value is not in the subgroup",
+            });
         }
 
-        let mut rng = rand::thread_rng();
-        let r = rng.gen_range(ubig!(0)..=&self.prime - 2);
+        Ok(())
+    }
 
-        let c1 = self.alpha.powmod(r.clone(), &self.prime);
-        let c2 = message * self.beta.powmod(r, &self.prime);
+    /// Encrypts a single block into a typed ciphertext.
+    #[inline]
+    pub fn encrypt_block(&self, bytes: &[u8]) -> Result<ElGamalCiphertext> {
+        self.encrypt_block_with_rng(bytes, &mut rand::thread_rng())
+    }
 
-        let c1_bytes = c1.to_le_bytes();
-        let c2_bytes = c2.to_le_bytes();
+    /// Encrypts a single block into a typed ciphertext, drawing the ephemeral key from `rng`.
+    pub fn encrypt_block_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<ElGamalCiphertext> {
+        let message = self.params.encode(bytes)?;
+        self.encrypt_element_with_rng(&message, rng)
+    }
 
-        let output = vec![c1_bytes, c2_bytes];
-        let output = marshal_bytes(&output);
-        Ok(output)
+    /// Encrypts an element of the subgroup as is, without encoding it first.
+    ///
+    /// Ciphertexts of elements multiply under [`Homomorphic::mul`] into a ciphertext
+    /// of the product of the elements.
+    #[inline]
+    pub fn encrypt_element(&self, element: &UBig) -> Result<ElGamalCiphertext> {
+        self.encrypt_element_with_rng(element, &mut rand::thread_rng())
     }
-}
 
-impl Verifier for ElGamalPublic {
-    fn verify(
+    /// Like [`ElGamalPublic::encrypt_element`], but draws the ephemeral key from `rng`.
+    pub fn encrypt_element_with_rng<R: RngCore + CryptoRng>(
         &self,
-        expected: &[u8],
-        signed_data: &[u8],
-        hashf: fn(&[u8]) -> Vec<u8>,
-    ) -> Result<bool> {
-        let sd = unmarshal_bytes(signed_data);
-        let sigma = UBig::from_le_bytes(&sd[0]);
-        let delta = UBig::from_le_bytes(&sd[1]);
+        element: &UBig,
+        rng: &mut R,
+    ) -> Result<ElGamalCiphertext> {
+        let ElGamalParams { p, q, g } = &self.params;
+        check_subgroup(element, p, q, "element is not in the subgroup")?;
+
+        let r = rng.gen_range(ubig!(1)..q.clone());
+        let c1 = g.powmod(r.clone(), p);
+        let c2 = (element * self.beta.powmod(r, p)) % p;
+
+        Ok(ElGamalCiphertext { c1, c2 })
+    }
+
+    /// Encrypts `g ^ value`, the exponential ElGamal encoding of `value`.
+    ///
+    /// Under exponential encoding [`Homomorphic::mul`] adds the plaintexts and
+    /// [`Homomorphic::pow`] multiplies them by a scalar. The result is recovered
+    /// with [`super::ElGamalPrivate::decrypt_exponent`] as long as it stays small.
+    #[inline]
+    pub fn encrypt_exponent(&self, value: u64) -> Result<ElGamalCiphertext> {
+        self.encrypt_exponent_with_rng(value, &mut rand::thread_rng())
+    }
+
+    /// Like [`ElGamalPublic::encrypt_exponent`], but draws the ephemeral key from `rng`.
+    pub fn encrypt_exponent_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        value: u64,
+        rng: &mut R,
+    ) -> Result<ElGamalCiphertext> {
+        let element = self.params.g.powmod(UBig::from(value), &self.params.p);
+        self.encrypt_element_with_rng(&element, rng)
+    }
+
+    /// Verifies a typed signature over an already computed hash of the message.
+    pub fn verify_signature(&self, hash: &[u8], signature: &ElGamalSignature) -> Result<bool> {
+        let ElGamalSignature { sigma, delta } = signature;
+
+        check_subgroup(
+            sigma,
+            &self.params.p,
+            &self.params.q,
+            "sigma is not in the subgroup",
+        )?;
+        if *delta >= self.params.q {
+            return Err(Error::OutOfRange {
+                algorithm: Algorithm::ElGamal,
+                reason: "delta is not smaller than the group order",
+            });
+        }
 
         let lhs = {
-            let a = self.beta.powmod(sigma.clone(), &self.prime);
-            let b = sigma.powmod(delta, &self.prime);
-            (a * b) % &self.prime
+            let a = self.beta.powmod(sigma.clone(), &self.params.p);
+            let b = sigma.powmod(delta.clone(), &self.params.p);
+            (a * b) % &self.params.p
         };
 
         let rhs = {
-            let hash = UBig::from_le_bytes(&hashf(expected));
-            self.alpha.powmod(hash, &self.prime)
+            let hash = bits_to_int(hash, self.params.q.bit_len()) % &self.params.q;
+            self.params.g.powmod(hash, &self.params.p)
         };
 
         Ok(lhs == rhs)
     }
 }
+
+/// ElGamal is multiplicatively homomorphic in the group elements it encrypts.
+impl Homomorphic for ElGamalPublic {
+    type Ciphertext = ElGamalCiphertext;
+
+    fn mul(&self, a: &ElGamalCiphertext, b: &ElGamalCiphertext) -> Result<ElGamalCiphertext> {
+        let ElGamalParams { p, q, .. } = &self.params;
+        for value in [&a.c1, &a.c2, &b.c1, &b.c2] {
+            check_subgroup(value, p, q, "ciphertext is not in the subgroup")?;
+        }
+
+        Ok(ElGamalCiphertext {
+            c1: (&a.c1 * &b.c1) % p,
+            c2: (&a.c2 * &b.c2) % p,
+        })
+    }
+
+    fn pow(&self, ciphertext: &ElGamalCiphertext, exponent: &UBig) -> Result<ElGamalCiphertext> {
+        let ElGamalParams { p, q, .. } = &self.params;
+        for value in [&ciphertext.c1, &ciphertext.c2] {
+            check_subgroup(value, p, q, "ciphertext is not in the subgroup")?;
+        }
+
+        Ok(ElGamalCiphertext {
+            c1: ciphertext.c1.powmod(exponent.clone(), p),
+            c2: ciphertext.c2.powmod(exponent.clone(), p),
+        })
+    }
+
+    /// Multiplies the ciphertext with a fresh encryption of `1`.
+    fn rerandomize_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        ciphertext: &ElGamalCiphertext,
+        rng: &mut R,
+    ) -> Result<ElGamalCiphertext> {
+        let one = self.encrypt_element_with_rng(&ubig!(1), rng)?;
+        self.mul(ciphertext, &one)
+    }
+}
+
+impl PublicKey for ElGamalPublic {
+    #[inline]
+    fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        self.encrypt_block(bytes).map(|c| c.to_bytes())
+    }
+
+    #[inline]
+    fn encrypt_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        self.encrypt_block_with_rng(bytes, rng)
+            .map(|c| c.to_bytes())
+    }
+
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.params.p.bit_len()
+    }
+
+    /// Messages are encoded below the group order `q` rather than the modulus.
+    #[inline]
+    fn max_message_size(&self) -> usize {
+        self.params.max_message_size()
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::ElGamal
+    }
+}
+
+impl Verifier for ElGamalPublic {
+    #[inline]
+    fn verify_hash(&self, hash: &[u8], signed_data: &[u8]) -> Result<bool> {
+        self.verify_signature(hash, &ElGamalSignature::from_bytes(signed_data)?)
+    }
+}
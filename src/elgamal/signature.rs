@@ -0,0 +1,53 @@
+use ibig::UBig;
+
+use crate::result::{Error, Result};
+use crate::utils::{
+    marshal_bytes, tag_bytes, to_fixed_le_bytes, unmarshal_bytes, untag_bytes, TypeTag,
+};
+
+/// An ElGamal signature over the hash of a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElGamalSignature {
+    /// The commitment `g ^ r mod p`.
+    pub sigma: UBig,
+
+    /// The response `(hash - key * sigma) * r^-1 mod q`.
+    pub delta: UBig,
+}
+
+impl ElGamalSignature {
+    /// Encodes both components as marshalled little-endian bytes behind the type tag.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let components = marshal_bytes(&vec![self.sigma.to_le_bytes(), self.delta.to_le_bytes()]);
+        tag_bytes(TypeTag::ElGamalSignature, &components)
+    }
+
+    /// Encodes both components as marshalled little-endian bytes, each exactly
+    /// `length` bytes long, so that every signature of a key has the same size.
+    pub(crate) fn to_fixed_bytes(&self, length: usize) -> Vec<u8> {
+        let sigma = to_fixed_le_bytes(&self.sigma, length);
+        let delta = to_fixed_le_bytes(&self.delta, length);
+        tag_bytes(
+            TypeTag::ElGamalSignature,
+            &marshal_bytes(&vec![sigma, delta]),
+        )
+    }
+
+    /// Decodes a signature produced by [`ElGamalSignature::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let [sigma, delta] = <[Vec<u8>; 2]>::try_from(unmarshal_bytes(untag_bytes(
+            TypeTag::ElGamalSignature,
+            bytes,
+        )?)?)
+        .map_err(|_| Error::MalformedInput {
+            reason: "expected exactly two components",
+            chunk: None,
+        })?;
+
+        Ok(Self {
+            sigma: UBig::from_le_bytes(&sigma),
+            delta: UBig::from_le_bytes(&delta),
+        })
+    }
+}
@@ -1,14 +1,24 @@
-use crate::{elgamal::ElGamalKeyPair, PrivateKey, PublicKey};
-use crate::{Signer, Verifier};
+use ibig::UBig;
+use rand::{rngs::StdRng, SeedableRng};
+use sha2::{Digest, Sha256};
+
+use crate::elgamal::{
+    ElGamalCiphertext, ElGamalKeyPair, ElGamalParams, ElGamalSignature, NamedGroup,
+    MAX_EXPONENT_BOUND,
+};
+use crate::result::Error;
+use crate::utils::{marshal_bytes, tag_bytes, PowMod, TypeTag};
+use crate::{Homomorphic, Signer, Verifier};
+use crate::{PrivateKey, PublicKey};
 
 const MESSAGE: [u8; 445] = *b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.";
 
 #[test]
 fn test_encrypt_decrypt() {
-    let key = ElGamalKeyPair::new(128, 5);
+    let key = ElGamalKeyPair::new(128, 5).unwrap();
 
-    let encrypted = key.encrypt_chunked(&MESSAGE, 8).unwrap();
-    let decrypted = key.decrypt_chunked(&encrypted, 8).unwrap();
+    let encrypted = key.encrypt_chunked(&MESSAGE).unwrap();
+    let decrypted = key.decrypt_chunked(&encrypted).unwrap();
     assert_eq!(MESSAGE, decrypted.as_slice());
 }
 
@@ -22,10 +32,338 @@ fn test_sign_verify() {
             .to_vec()
     }
 
-    let key = ElGamalKeyPair::new(128, 5);
+    let key = ElGamalKeyPair::new(128, 5).unwrap();
 
     let signed = key.sign_chunked(&MESSAGE, hashf, 16).unwrap();
     let verified = key.verify_chunked(&MESSAGE, &signed, hashf, 16).unwrap();
 
     assert!(verified);
 }
+
+#[test]
+fn test_sign_verify_digest() {
+    let key = ElGamalKeyPair::new(128, 5).unwrap();
+
+    let mut digest = Sha256::new();
+    for chunk in MESSAGE.chunks(64) {
+        digest.update(chunk);
+    }
+
+    let signed = key.sign_digest(digest).unwrap();
+    assert!(key
+        .verify_digest(Sha256::new_with_prefix(MESSAGE), &signed)
+        .unwrap());
+    assert!(!key
+        .verify_digest(Sha256::new_with_prefix(b"Lorem"), &signed)
+        .unwrap());
+}
+
+#[test]
+fn test_validate_and_range_checks() {
+    let key = ElGamalKeyPair::new(128, 5).unwrap();
+    assert!(key.public.validate().is_ok());
+
+    let mut weak = key.public.clone();
+    weak.params.g = UBig::from(1u8);
+    assert!(matches!(weak.validate(), Err(Error::InvalidKey { .. })));
+
+    let outside = ElGamalCiphertext {
+        c1: key.public.params.p.clone(),
+        c2: UBig::from(1u8),
+    }
+    .to_bytes();
+    assert!(matches!(
+        key.decrypt(&outside),
+        Err(Error::OutOfRange { .. })
+    ));
+
+    let zero_sigma = ElGamalSignature {
+        sigma: UBig::from(0u8),
+        delta: UBig::from(1u8),
+    }
+    .to_bytes();
+    assert!(matches!(
+        key.verify_hash(b"Lorem", &zero_sigma),
+        Err(Error::OutOfRange { .. })
+    ));
+
+    let mut oversized = key.private.clone();
+    oversized.key = key.public.params.q.clone().into();
+    let ciphertext = key.public.encrypt_block(b"Lorem").unwrap();
+    assert!(matches!(
+        oversized.decrypt_element(&ciphertext),
+        Err(Error::InvalidKey { .. })
+    ));
+}
+
+#[test]
+fn test_typed_ciphertext_and_signature() {
+    let key = ElGamalKeyPair::new(128, 5).unwrap();
+
+    let ciphertext = key.public.encrypt_block(b"Lorem").unwrap();
+    assert!(ciphertext.c2 < key.public.params.p);
+
+    let parsed = ElGamalCiphertext::from_bytes(&ciphertext.to_bytes()).unwrap();
+    assert_eq!(parsed, ciphertext);
+    assert_eq!(key.private.decrypt_block(&parsed).unwrap(), b"Lorem");
+
+    let hash = Sha256::digest(MESSAGE);
+    let signature = key.private.create_signature(&hash).unwrap();
+    let parsed = ElGamalSignature::from_bytes(&signature.to_bytes()).unwrap();
+    assert_eq!(parsed, signature);
+    assert!(key.public.verify_signature(&hash, &parsed).unwrap());
+
+    assert!(matches!(
+        ElGamalSignature::from_bytes(&tag_bytes(
+            TypeTag::ElGamalSignature,
+            &marshal_bytes(&vec![vec![1]])
+        )),
+        Err(Error::MalformedInput { .. })
+    ));
+    assert!(matches!(
+        ElGamalSignature::from_bytes(&ciphertext.to_bytes()),
+        Err(Error::MalformedInput { .. })
+    ));
+}
+
+#[test]
+fn test_seeded_rng_is_deterministic() {
+    let key = ElGamalKeyPair::new_with_rng(128, 5, &mut StdRng::seed_from_u64(7)).unwrap();
+    let same = ElGamalKeyPair::new_with_rng(128, 5, &mut StdRng::seed_from_u64(7)).unwrap();
+    assert_eq!(key.public.beta, same.public.beta);
+
+    let encrypt = |seed| {
+        key.encrypt_chunked_with_rng(&MESSAGE, &mut StdRng::seed_from_u64(seed))
+            .unwrap()
+    };
+    assert_eq!(encrypt(1), encrypt(1));
+    assert_ne!(encrypt(1), encrypt(2));
+    assert_eq!(key.decrypt_chunked(&encrypt(1)).unwrap(), MESSAGE);
+
+    let hash = Sha256::digest(MESSAGE);
+    let sign = |seed| {
+        key.sign_hash_with_rng(&hash, &mut StdRng::seed_from_u64(seed))
+            .unwrap()
+    };
+    assert_eq!(sign(1), sign(1));
+    assert!(key.verify_hash(&hash, &sign(1)).unwrap());
+}
+
+#[test]
+fn test_deterministic_signature() {
+    let key = ElGamalKeyPair::new(128, 5).unwrap();
+
+    let hash = Sha256::digest(MESSAGE);
+    let signed = key.sign_hash(&hash).unwrap();
+    assert_eq!(signed, key.sign_hash(&hash).unwrap());
+    assert!(key.verify_hash(&hash, &signed).unwrap());
+
+    let other = Sha256::digest(b"Lorem");
+    let first = ElGamalSignature::from_bytes(&signed).unwrap();
+    let second = key.private.create_signature(&other).unwrap();
+    assert_ne!(first.sigma, second.sigma);
+    assert!(key.public.verify_signature(&other, &second).unwrap());
+}
+
+#[test]
+fn test_safe_prime_group() {
+    let key = ElGamalKeyPair::new(128, 5).unwrap();
+    let ElGamalParams { p, q, g } = &key.public.params;
+
+    assert_eq!(*p, q * 2u8 + 1u8);
+    assert_eq!(g.powmod(q.clone(), p), UBig::from(1u8));
+
+    let mut small = key.public.clone();
+    small.params.g = p - 1u8;
+    assert!(matches!(small.validate(), Err(Error::InvalidKey { .. })));
+
+    let outside = ElGamalCiphertext {
+        c1: p - 1u8,
+        c2: UBig::from(1u8),
+    };
+    assert!(matches!(
+        key.private.decrypt_block(&outside),
+        Err(Error::OutOfRange { .. })
+    ));
+}
+
+#[test]
+fn test_shared_params() {
+    let params = ElGamalParams::generate(128, 5).unwrap();
+    assert!(params.validate().is_ok());
+
+    let alice = ElGamalKeyPair::from_params(params.clone());
+    let bob = ElGamalKeyPair::from_params(params.clone());
+    assert_eq!(alice.public.params, bob.public.params);
+    assert_ne!(alice.public.beta, bob.public.beta);
+
+    let encrypted = bob.public.encrypt_chunked(&MESSAGE).unwrap();
+    assert_eq!(bob.decrypt_chunked(&encrypted).unwrap(), MESSAGE);
+    assert!(alice
+        .decrypt_chunked(&encrypted)
+        .map_or(true, |m| m != MESSAGE));
+
+    let mut broken = params;
+    broken.q += 2u8;
+    assert!(matches!(broken.validate(), Err(Error::InvalidKey { .. })));
+}
+
+#[test]
+fn test_named_groups() {
+    for group in NamedGroup::ALL {
+        let params = group.params();
+        assert_eq!(params.p.bit_len(), group.bit_length());
+        assert_eq!(params.p, &params.q * 2u8 + 1u8);
+        assert_eq!(NamedGroup::identify(&params), Some(group));
+    }
+
+    let params = ElGamalParams::named(NamedGroup::Ffdhe2048);
+    assert_eq!(
+        params.g.powmod(params.q.clone(), &params.p),
+        UBig::from(1u8)
+    );
+
+    let key = ElGamalKeyPair::from_params(params);
+    let encrypted = key.encrypt_chunked(&MESSAGE).unwrap();
+    assert_eq!(key.decrypt_chunked(&encrypted).unwrap(), MESSAGE);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_typed_serialization() {
+    let key = ElGamalKeyPair::new(128, 5).unwrap();
+
+    let ciphertext = key.public.encrypt_block(b"Lorem").unwrap();
+    let json = serde_json::to_string(&ciphertext).unwrap();
+    assert_eq!(
+        serde_json::from_str::<ElGamalCiphertext>(&json).unwrap(),
+        ciphertext
+    );
+
+    let signature = key
+        .private
+        .create_signature(&Sha256::digest(MESSAGE))
+        .unwrap();
+    let json = serde_json::to_string(&signature).unwrap();
+    assert_eq!(
+        serde_json::from_str::<ElGamalSignature>(&json).unwrap(),
+        signature
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_named_group_serialization() {
+    let key = ElGamalKeyPair::from_params(NamedGroup::Modp2048.params());
+    let json = serde_json::to_string(&key.public).unwrap();
+    assert!(json.contains("\"params\":\"modp2048\""));
+
+    let parsed: crate::elgamal::ElGamalPublic = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.params, key.public.params);
+    assert_eq!(parsed.beta, key.public.beta);
+
+    let custom = ElGamalParams::generate(128, 5).unwrap();
+    let json = serde_json::to_string(&custom).unwrap();
+    assert_eq!(
+        serde_json::from_str::<ElGamalParams>(&json).unwrap(),
+        custom
+    );
+}
+
+#[test]
+fn test_quadratic_residue_encoding() {
+    let key = ElGamalKeyPair::new(128, 5).unwrap();
+    let ElGamalParams { p, q, .. } = &key.public.params;
+
+    for message in [&b""[..], b"\x00", b"Lorem", &[0xff; 15]] {
+        let element = key.public.params.encode(message).unwrap();
+        assert_eq!(element.powmod(q.clone(), p), UBig::from(1u8));
+        assert_eq!(
+            key.public.params.decode(&element).unwrap(),
+            UBig::from_le_bytes(message).to_le_bytes()
+        );
+
+        let ciphertext = key.public.encrypt_block(message).unwrap();
+        assert_eq!(ciphertext.c2.powmod(q.clone(), p), UBig::from(1u8));
+    }
+
+    let too_large = q.to_le_bytes();
+    assert!(matches!(
+        key.public.params.encode(&too_large),
+        Err(Error::MessageTooLarge { .. })
+    ));
+
+    let mut ciphertext = key.public.encrypt_block(b"Lorem").unwrap();
+    ciphertext.c2 = p - &ciphertext.c2;
+    assert!(matches!(
+        key.private.decrypt_block(&ciphertext),
+        Err(Error::OutOfRange { .. })
+    ));
+}
+
+#[test]
+fn test_homomorphic_operations() {
+    let key = ElGamalKeyPair::new(128, 5).unwrap();
+    let ElGamalParams { p, g, .. } = &key.public.params;
+
+    let a = g.powmod(UBig::from(5u8), p);
+    let b = g.powmod(UBig::from(7u8), p);
+    let ca = key.public.encrypt_element(&a).unwrap();
+    let cb = key.public.encrypt_element(&b).unwrap();
+
+    let product = key.public.mul(&ca, &cb).unwrap();
+    assert_eq!(
+        key.private.decrypt_element(&product).unwrap(),
+        (&a * &b) % p
+    );
+
+    let squared = key.public.pow(&ca, &UBig::from(2u8)).unwrap();
+    assert_eq!(
+        key.private.decrypt_element(&squared).unwrap(),
+        (&a * &a) % p
+    );
+
+    let fresh = key.public.rerandomize(&ca).unwrap();
+    assert_ne!(fresh, ca);
+    assert_eq!(key.private.decrypt_element(&fresh).unwrap(), a);
+}
+
+#[test]
+fn test_exponential_tally() {
+    let key = ElGamalKeyPair::new(128, 5).unwrap();
+
+    let votes = [1, 0, 1, 1, 0, 1, 1];
+    let tally = votes
+        .iter()
+        .map(|&vote| key.public.encrypt_exponent(vote).unwrap())
+        .reduce(|a, b| key.public.mul(&a, &b).unwrap())
+        .unwrap();
+
+    assert_eq!(key.private.decrypt_exponent(&tally, 100).unwrap(), 5);
+
+    let tripled = key.public.pow(&tally, &UBig::from(3u8)).unwrap();
+    assert_eq!(key.private.decrypt_exponent(&tripled, 100).unwrap(), 15);
+    assert!(matches!(
+        key.private.decrypt_exponent(&tripled, 10),
+        Err(Error::OutOfRange { .. })
+    ));
+    assert!(matches!(
+        key.private
+            .decrypt_exponent(&tripled, MAX_EXPONENT_BOUND + 1),
+        Err(Error::OutOfRange { .. })
+    ));
+
+    // p - 1 has order 2, so it lies in the group but outside the subgroup.
+    let outside = ElGamalCiphertext {
+        c1: &key.public.params.p - 1u8,
+        ..tally.clone()
+    };
+    assert!(matches!(
+        key.public.mul(&tally, &outside),
+        Err(Error::OutOfRange { .. })
+    ));
+    assert!(matches!(
+        key.public.pow(&outside, &UBig::from(2u8)),
+        Err(Error::OutOfRange { .. })
+    ));
+}
@@ -0,0 +1,28 @@
+use ibig::UBig;
+
+use crate::result::Result;
+use crate::utils::{marshal_bytes, tag_bytes, unmarshal_bytes, untag_bytes, TypeTag};
+
+/// A message encrypted bit by bit with a Goldwasser–Micali public key.
+///
+/// Bit `j` of byte `i` of the message, counting from the least significant bit,
+/// is encrypted into element `8 * i + j`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GoldwasserMicaliCiphertext(pub Vec<UBig>);
+
+impl GoldwasserMicaliCiphertext {
+    /// Encodes the encrypted bits as marshalled little-endian bytes behind the type tag.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let bits: Vec<Vec<u8>> = self.0.iter().map(|bit| bit.to_le_bytes()).collect();
+        tag_bytes(TypeTag::GoldwasserMicaliCiphertext, &marshal_bytes(&bits))
+    }
+
+    /// Decodes a ciphertext produced by [`GoldwasserMicaliCiphertext::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let bits = unmarshal_bytes(untag_bytes(TypeTag::GoldwasserMicaliCiphertext, bytes)?)?;
+        Ok(Self(
+            bits.iter().map(|bit| UBig::from_le_bytes(bit)).collect(),
+        ))
+    }
+}
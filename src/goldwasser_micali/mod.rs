@@ -0,0 +1,12 @@
+mod ciphertext;
+mod pair;
+mod private;
+mod public;
+
+pub use ciphertext::GoldwasserMicaliCiphertext;
+pub use pair::GoldwasserMicaliKeyPair;
+pub use private::GoldwasserMicaliPrivate;
+pub use public::GoldwasserMicaliPublic;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,107 @@
+use ibig::UBig;
+use rand::{CryptoRng, RngCore};
+
+use super::{GoldwasserMicaliPrivate, GoldwasserMicaliPublic};
+use crate::result::{Algorithm, Result};
+use crate::utils::{check_prime_bits, gen_prime};
+use crate::{PrivateKey, PublicKey};
+
+/// A key pair for the Goldwasser–Micali cryptosystem.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GoldwasserMicaliKeyPair {
+    /// The public key for encryption.
+    pub public: GoldwasserMicaliPublic,
+
+    /// The private key for decryption.
+    pub private: GoldwasserMicaliPrivate,
+}
+
+impl GoldwasserMicaliKeyPair {
+    /// Generates a new Goldwasser–Micali key pair with the specified bit length and persistence level.
+    ///
+    /// * `bit_length`: The desired bit length of each of the two primes.
+    /// * `persistence`: The number of iterations for checking numbers for primality.
+    ///
+    /// Returns a newly generated `GoldwasserMicaliKeyPair` instance, or
+    /// [`Error::KeyGeneration`](crate::result::Error::KeyGeneration) if `bit_length` is too small.
+    #[inline]
+    pub fn new(bit_length: usize, persistence: usize) -> Result<Self> {
+        Self::new_with_rng(bit_length, persistence, &mut rand::thread_rng())
+    }
+
+    /// Generates a new Goldwasser–Micali key pair, drawing all randomness from `rng`.
+    ///
+    /// The same seeded generator always yields the same key pair.
+    pub fn new_with_rng<R: RngCore + CryptoRng>(
+        bit_length: usize,
+        persistence: usize,
+        rng: &mut R,
+    ) -> Result<Self> {
+        check_prime_bits(Algorithm::GoldwasserMicali, bit_length)?;
+
+        // Blum primes make -1 a non-residue modulo both, so n - 1 serves as the public non-residue.
+        let p = gen_prime(bit_length, persistence, rng, |p| p % 4u8 == 3u8);
+        let q = loop {
+            let q: UBig = gen_prime(bit_length, persistence, rng, |q| q % 4u8 == 3u8);
+            if q != p {
+                break q;
+            }
+        };
+
+        let divisor = &p * &q;
+        Ok(Self {
+            public: GoldwasserMicaliPublic {
+                non_residue: &divisor - 1u8,
+                divisor,
+            },
+            private: GoldwasserMicaliPrivate {
+                prime_1: p.into(),
+                prime_2: q.into(),
+            },
+        })
+    }
+}
+
+impl PrivateKey for GoldwasserMicaliKeyPair {
+    #[inline]
+    fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.private.decrypt(message)
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::GoldwasserMicali
+    }
+}
+
+impl PublicKey for GoldwasserMicaliKeyPair {
+    #[inline]
+    fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        self.public.encrypt(bytes)
+    }
+
+    #[inline]
+    fn encrypt_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        self.public.encrypt_with_rng(bytes, rng)
+    }
+
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.public.bit_length()
+    }
+
+    #[inline]
+    fn max_message_size(&self) -> usize {
+        self.public.max_message_size()
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::GoldwasserMicali
+    }
+}
@@ -0,0 +1,67 @@
+use super::GoldwasserMicaliCiphertext;
+use crate::result::{Algorithm, Error};
+use crate::secret::SecretUBig;
+use crate::utils::ct_powmod;
+use crate::{keypair::PrivateKey, result::Result};
+
+/// Private key for the Goldwasser–Micali cryptosystem.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GoldwasserMicaliPrivate {
+    /// First prime factor of the public modulus.
+    pub prime_1: SecretUBig,
+
+    /// Second prime factor of the public modulus.
+    pub prime_2: SecretUBig,
+}
+
+impl GoldwasserMicaliPrivate {
+    /// Decrypts a single typed ciphertext block.
+    ///
+    /// An element decrypts to `0` if it is a quadratic residue modulo the first prime,
+    /// which Euler's criterion decides, and to `1` otherwise.
+    pub fn decrypt_block(&self, ciphertext: &GoldwasserMicaliCiphertext) -> Result<Vec<u8>> {
+        let p = self.prime_1.expose();
+        let n = p * self.prime_2.expose();
+
+        if !ciphertext.0.len().is_multiple_of(8) {
+            return Err(Error::MalformedInput {
+                reason: "ciphertext does not encrypt whole bytes",
+                chunk: None,
+            });
+        }
+
+        if ciphertext.0.iter().any(|bit| *bit >= n) {
+            return Err(Error::OutOfRange {
+                algorithm: Algorithm::GoldwasserMicali,
+                reason: "ciphertext is not smaller than the modulus",
+            });
+        }
+
+        let exponent = (p - 1u8) >> 1;
+        let bytes = ciphertext
+            .0
+            .chunks(8)
+            .map(|bits| {
+                bits.iter().enumerate().fold(0u8, |byte, (j, bit)| {
+                    let residue = ct_powmod(&(bit % p), &exponent, p) == 1u8.into();
+                    byte | ((!residue as u8) << j)
+                })
+            })
+            .collect();
+
+        Ok(bytes)
+    }
+}
+
+impl PrivateKey for GoldwasserMicaliPrivate {
+    #[inline]
+    fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.decrypt_block(&GoldwasserMicaliCiphertext::from_bytes(message)?)
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::GoldwasserMicali
+    }
+}
@@ -0,0 +1,220 @@
+use ibig::{ubig, UBig};
+use rand::{CryptoRng, Rng, RngCore};
+
+use super::GoldwasserMicaliCiphertext;
+use crate::homomorphic::Homomorphic;
+use crate::result::{Algorithm, Error};
+use crate::utils::{has_small_factor, jacobi, PowMod, MIN_MODULUS_BITS};
+use crate::{keypair::PublicKey, result::Result};
+
+/// Public key for the Goldwasser–Micali cryptosystem.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GoldwasserMicaliPublic {
+    /// Public modulus `n`, the product of two primes.
+    pub divisor: UBig,
+
+    /// A quadratic non-residue modulo both primes, whose Jacobi symbol modulo `n` is 1.
+    pub non_residue: UBig,
+}
+
+impl GoldwasserMicaliPublic {
+    /// Checks that the key is plausible before it is used.
+    ///
+    /// The modulus must be odd, at least `MIN_MODULUS_BITS` wide and free of small factors,
+    /// and the Jacobi symbol of the non-residue modulo `n` must be 1. Whether it really is a
+    /// non-residue cannot be checked without the factors, which is what the scheme relies on.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |reason| {
+            Err(Error::InvalidKey {
+                algorithm: Algorithm::GoldwasserMicali,
+                reason,
+            })
+        };
+
+        if self.divisor.bit_len() < MIN_MODULUS_BITS {
+            return invalid("modulus is too small");
+        }
+
+        if &self.divisor % 2u8 == 0u8 {
+            return invalid("modulus is even");
+        }
+
+        if has_small_factor(&self.divisor) {
+            return invalid("modulus has a small factor");
+        }
+
+        if self.non_residue >= self.divisor || jacobi(&self.non_residue, &self.divisor) != 1 {
+            return invalid("non-residue has a Jacobi symbol other than 1");
+        }
+
+        Ok(())
+    }
+
+    /// Draws a random square `y ^ 2 mod n` with `y` invertible modulo `n`.
+    fn random_square<R: RngCore + CryptoRng>(&self, rng: &mut R) -> UBig {
+        let y = loop {
+            let y = rng.gen_range(ubig!(1)..self.divisor.clone());
+            if y.gcd(&self.divisor) == ubig!(1) {
+                break y;
+            }
+        };
+
+        y.powmod(ubig!(2), &self.divisor)
+    }
+
+    /// Ensures that every element lies below the modulus.
+    fn check_ciphertext(&self, ciphertext: &GoldwasserMicaliCiphertext) -> Result<()> {
+        match ciphertext.0.iter().all(|bit| *bit < self.divisor) {
+            true => Ok(()),
+            false => Err(Error::OutOfRange {
+                algorithm: Algorithm::GoldwasserMicali,
+                reason: "ciphertext is not smaller than the modulus",
+            }),
+        }
+    }
+
+    /// Encrypts a single block into a typed ciphertext.
+    #[inline]
+    pub fn encrypt_block(&self, bytes: &[u8]) -> Result<GoldwasserMicaliCiphertext> {
+        self.encrypt_block_with_rng(bytes, &mut rand::thread_rng())
+    }
+
+    /// Encrypts a single block into a typed ciphertext, drawing the randomness from `rng`.
+    ///
+    /// Every bit `b` becomes `y ^ 2 * x ^ b mod n` for a fresh random `y`, so a zero bit
+    /// encrypts to a quadratic residue and a one bit to a non-residue.
+    pub fn encrypt_block_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<GoldwasserMicaliCiphertext> {
+        let mut bits = Vec::with_capacity(bytes.len() * 8);
+        for byte in bytes {
+            for j in 0..8 {
+                let square = self.random_square(rng);
+                bits.push(match (byte >> j) & 1 {
+                    0 => square,
+                    _ => (square * &self.non_residue) % &self.divisor,
+                });
+            }
+        }
+
+        Ok(GoldwasserMicaliCiphertext(bits))
+    }
+
+    /// Computes an encryption of the bitwise XOR of both plaintexts.
+    #[inline]
+    pub fn xor(
+        &self,
+        a: &GoldwasserMicaliCiphertext,
+        b: &GoldwasserMicaliCiphertext,
+    ) -> Result<GoldwasserMicaliCiphertext> {
+        self.mul(a, b)
+    }
+
+    /// Computes an encryption of the bitwise complement of the plaintext.
+    pub fn not(
+        &self,
+        ciphertext: &GoldwasserMicaliCiphertext,
+    ) -> Result<GoldwasserMicaliCiphertext> {
+        self.check_ciphertext(ciphertext)?;
+
+        Ok(GoldwasserMicaliCiphertext(
+            ciphertext
+                .0
+                .iter()
+                .map(|bit| (bit * &self.non_residue) % &self.divisor)
+                .collect(),
+        ))
+    }
+}
+
+/// Goldwasser–Micali is homomorphic over single bits: [`Homomorphic::mul`] XORs the
+/// plaintexts bit by bit and [`Homomorphic::pow`] ANDs every bit with the parity of the exponent.
+impl Homomorphic for GoldwasserMicaliPublic {
+    type Ciphertext = GoldwasserMicaliCiphertext;
+
+    fn mul(
+        &self,
+        a: &GoldwasserMicaliCiphertext,
+        b: &GoldwasserMicaliCiphertext,
+    ) -> Result<GoldwasserMicaliCiphertext> {
+        self.check_ciphertext(a)?;
+        self.check_ciphertext(b)?;
+
+        if a.0.len() != b.0.len() {
+            return Err(Error::MalformedInput {
+                reason: "ciphertexts encrypt a different number of bits",
+                chunk: None,
+            });
+        }
+
+        Ok(GoldwasserMicaliCiphertext(
+            a.0.iter()
+                .zip(&b.0)
+                .map(|(a, b)| (a * b) % &self.divisor)
+                .collect(),
+        ))
+    }
+
+    fn pow(
+        &self,
+        ciphertext: &GoldwasserMicaliCiphertext,
+        exponent: &UBig,
+    ) -> Result<GoldwasserMicaliCiphertext> {
+        self.check_ciphertext(ciphertext)?;
+
+        Ok(GoldwasserMicaliCiphertext(
+            ciphertext
+                .0
+                .iter()
+                .map(|bit| bit.powmod(exponent.clone(), &self.divisor))
+                .collect(),
+        ))
+    }
+
+    /// Multiplies every element with a fresh random square.
+    fn rerandomize_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        ciphertext: &GoldwasserMicaliCiphertext,
+        rng: &mut R,
+    ) -> Result<GoldwasserMicaliCiphertext> {
+        self.check_ciphertext(ciphertext)?;
+
+        Ok(GoldwasserMicaliCiphertext(
+            ciphertext
+                .0
+                .iter()
+                .map(|bit| (bit * self.random_square(rng)) % &self.divisor)
+                .collect(),
+        ))
+    }
+}
+
+impl PublicKey for GoldwasserMicaliPublic {
+    #[inline]
+    fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        self.encrypt_block(bytes).map(|c| c.to_bytes())
+    }
+
+    #[inline]
+    fn encrypt_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        self.encrypt_block_with_rng(bytes, rng)
+            .map(|c| c.to_bytes())
+    }
+
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.divisor.bit_len()
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::GoldwasserMicali
+    }
+}
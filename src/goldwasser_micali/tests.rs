@@ -0,0 +1,48 @@
+use rand::{rngs::StdRng, SeedableRng};
+
+use super::{GoldwasserMicaliCiphertext, GoldwasserMicaliKeyPair};
+use crate::keypair::{PrivateKey, PublicKey};
+use crate::result::Error;
+use crate::Homomorphic;
+
+const MESSAGE: [u8; 445] = *b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.";
+
+#[test]
+fn test_encrypt_decrypt() {
+    let key = GoldwasserMicaliKeyPair::new(128, 5).unwrap();
+    assert!(key.public.validate().is_ok());
+
+    let encrypted = key.encrypt(&MESSAGE).unwrap();
+    let decrypted = key.decrypt(&encrypted).unwrap();
+    assert_eq!(MESSAGE, decrypted.as_slice());
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let first = key.encrypt_with_rng(b"Lorem", &mut rng).unwrap();
+    let second = key.encrypt_with_rng(b"Lorem", &mut rng).unwrap();
+    assert_ne!(first, second);
+}
+
+#[test]
+fn test_homomorphic_operations() {
+    let key = GoldwasserMicaliKeyPair::new(128, 5).unwrap();
+    let decrypt = |c: &GoldwasserMicaliCiphertext| key.private.decrypt_block(c).unwrap();
+
+    let ca = key.public.encrypt_block(&[0b1100_1010, 0x0f]).unwrap();
+    let cb = key.public.encrypt_block(&[0b1010_0110, 0xff]).unwrap();
+
+    let xor = key.public.xor(&ca, &cb).unwrap();
+    assert_eq!(decrypt(&xor), [0b0110_1100, 0xf0]);
+
+    let not = key.public.not(&ca).unwrap();
+    assert_eq!(decrypt(&not), [0b0011_0101, 0xf0]);
+
+    let fresh = key.public.rerandomize(&ca).unwrap();
+    assert_ne!(fresh, ca);
+    assert_eq!(decrypt(&fresh), [0b1100_1010, 0x0f]);
+
+    let short = key.public.encrypt_block(&[1]).unwrap();
+    assert!(matches!(
+        key.public.xor(&ca, &short),
+        Err(Error::MalformedInput { .. })
+    ));
+}
@@ -0,0 +1,41 @@
+use ibig::UBig;
+use rand::{CryptoRng, RngCore};
+
+use crate::result::Result;
+
+/// Operations on ciphertexts that carry over to the plaintexts they encrypt.
+///
+/// For multiplicatively homomorphic schemes [`Homomorphic::mul`] multiplies the plaintexts
+/// and [`Homomorphic::pow`] raises them to a power. For additively homomorphic schemes,
+/// such as exponential ElGamal, they add the plaintexts and multiply them by a scalar.
+pub trait Homomorphic {
+    /// The ciphertext type the operations work on.
+    type Ciphertext;
+
+    /// Combines two ciphertexts into an encryption of the product of their plaintexts.
+    fn mul(&self, a: &Self::Ciphertext, b: &Self::Ciphertext) -> Result<Self::Ciphertext>;
+
+    /// Turns a ciphertext into an encryption of its plaintext raised to `exponent`.
+    fn pow(&self, ciphertext: &Self::Ciphertext, exponent: &UBig) -> Result<Self::Ciphertext>;
+
+    /// Returns a fresh encryption of the same plaintext that cannot be linked to the original.
+    ///
+    /// Deterministic schemes, such as textbook RSA, fail with
+    /// [`Error::Unsupported`](crate::result::Error::Unsupported).
+    #[inline]
+    fn rerandomize(&self, ciphertext: &Self::Ciphertext) -> Result<Self::Ciphertext>
+    where
+        Self: Sized,
+    {
+        self.rerandomize_with_rng(ciphertext, &mut rand::thread_rng())
+    }
+
+    /// Like [`Homomorphic::rerandomize`], but draws the randomness from `rng`.
+    fn rerandomize_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        ciphertext: &Self::Ciphertext,
+        rng: &mut R,
+    ) -> Result<Self::Ciphertext>
+    where
+        Self: Sized;
+}
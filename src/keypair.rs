@@ -1,19 +1,204 @@
+use digest::Digest;
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroizing;
+
 use crate::{
-    result::Result,
-    utils::{marshal_bytes, unmarshal_bytes},
+    result::{Algorithm, Error, Result},
+    utils::{marshal_bytes, unmarshal_bytes, RecordKeys, RECORD_SIZE, SECRET_SIZE},
 };
 
+/// Byte appended to every chunk produced by [`PublicKey::encrypt_chunked`].
+///
+/// Integers drop their most significant zero bytes, so without a terminator
+/// a chunk ending in `0x00` would come back shorter than it went in.
+const CHUNK_TERMINATOR: u8 = 0x01;
+
+/// Returns the number of plaintext bytes that fit into one block next to the terminator.
+///
+/// Never returns zero; keys too small for even a single byte fail when the block is encrypted.
+fn chunk_size<K: PublicKey + ?Sized>(key: &K) -> usize {
+    key.max_message_size().saturating_sub(1).max(1)
+}
+
+/// Appends the terminator to the chunk and encrypts it as a single block with `encrypt`.
+fn encrypt_block<E>(chunk: &[u8], encrypt: &mut E) -> Result<Vec<u8>>
+where
+    E: FnMut(&[u8]) -> Result<Vec<u8>>,
+{
+    let mut block = chunk.to_vec();
+    block.push(CHUNK_TERMINATOR);
+    encrypt(&block)
+}
+
+/// Encrypts the slice in chunks of `chunk_size` bytes and marshals the results.
+fn encrypt_chunks<E>(bytes: &[u8], chunk_size: usize, encrypt: &mut E) -> Result<Vec<u8>>
+where
+    E: FnMut(&[u8]) -> Result<Vec<u8>>,
+{
+    let content: Vec<Vec<_>> = bytes
+        .chunks(chunk_size)
+        .map(|chunk| encrypt_block(chunk, encrypt))
+        .collect::<Result<_>>()?;
+
+    Ok(marshal_bytes(&content))
+}
+
+/// Builds the message described in [`PublicKey::encrypt_authenticated`] around `secret`.
+fn encrypt_sealed<E>(
+    bytes: &[u8],
+    chunk_size: usize,
+    secret: &[u8],
+    encrypt: &mut E,
+) -> Result<Vec<u8>>
+where
+    E: FnMut(&[u8]) -> Result<Vec<u8>>,
+{
+    let chunks: Vec<&[u8]> = match bytes.is_empty() {
+        true => vec![bytes],
+        false => bytes.chunks(RECORD_SIZE).collect(),
+    };
+
+    let mut content = Vec::with_capacity(chunks.len() + 1);
+    content.push(encrypt_chunks(secret, chunk_size, encrypt)?);
+
+    let keys = RecordKeys::derive(secret);
+    for (sequence, chunk) in chunks.iter().enumerate() {
+        let is_final = sequence + 1 == chunks.len();
+        content.push(keys.seal(sequence, is_final, chunk));
+    }
+
+    Ok(marshal_bytes(&content))
+}
+
+/// Decrypts a single block and strips its terminator.
+fn decrypt_block<K: PrivateKey + ?Sized>(key: &K, block: &[u8]) -> Result<Vec<u8>> {
+    let mut block = key.decrypt(block)?;
+    match block.pop() {
+        Some(CHUNK_TERMINATOR) => Ok(block),
+        _ => Err(Error::Padding {
+            algorithm: key.algorithm(),
+            chunk: None,
+        }),
+    }
+}
+
+/// Splits `data` into the chunks covered by [`Signer::sign_chunked`].
+///
+/// An empty message still yields a single empty chunk so that it is signed as well.
+fn signed_chunks(data: &[u8], chunk_size: usize) -> Vec<&[u8]> {
+    match data.is_empty() {
+        true => vec![data],
+        false => data.chunks(chunk_size).collect(),
+    }
+}
+
+/// Signs the chunks of `data` with `sign` and marshals the signatures.
+fn sign_chunks<S>(data: &[u8], chunk_size: usize, sign: &mut S) -> Result<Vec<u8>>
+where
+    S: FnMut(&[u8]) -> Result<Vec<u8>>,
+{
+    Ok(marshal_bytes(
+        &signed_chunks(data, chunk_size)
+            .into_iter()
+            .enumerate()
+            .map(|(index, chunk)| sign(&bind_chunk(index, data.len(), chunk)))
+            .collect::<Result<_>>()?,
+    ))
+}
+
+/// Prefixes a chunk with its index and the length of the whole message.
+///
+/// This binds every chunk signature to its position and to the message it
+/// came from, so signatures cannot be dropped, reordered or moved between messages.
+fn bind_chunk(index: usize, total_length: usize, chunk: &[u8]) -> Vec<u8> {
+    let mut bound = Vec::with_capacity(16 + chunk.len());
+    bound.extend((index as u64).to_le_bytes());
+    bound.extend((total_length as u64).to_le_bytes());
+    bound.extend(chunk);
+    bound
+}
+
 pub trait PublicKey {
     /// Encrypts a byte slice using the public key.
     fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>>;
 
-    fn encrypt_chunked(&self, bytes: &[u8], chunk_size: usize) -> Result<Vec<u8>> {
-        let content: Vec<Vec<_>> = bytes
-            .chunks(chunk_size)
-            .flat_map(|chunk| self.encrypt(chunk))
-            .collect();
+    /// Encrypts a byte slice, drawing any randomness the scheme needs from `rng`.
+    ///
+    /// Deterministic schemes ignore the generator and behave like [`PublicKey::encrypt`].
+    #[inline]
+    fn encrypt_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        _rng: &mut R,
+    ) -> Result<Vec<u8>>
+    where
+        Self: Sized,
+    {
+        self.encrypt(bytes)
+    }
+
+    /// Returns the bit length of the key's modulus.
+    fn bit_length(&self) -> usize;
+
+    /// Returns the cryptosystem the key belongs to.
+    fn algorithm(&self) -> Algorithm;
+
+    /// Returns the maximum number of bytes a single call to [`PublicKey::encrypt`] accepts.
+    #[inline]
+    fn max_message_size(&self) -> usize {
+        self.bit_length().saturating_sub(1) / 8
+    }
+
+    /// Splits the slice into chunks sized after the key, encrypts each and marshals the results.
+    fn encrypt_chunked(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        encrypt_chunks(bytes, chunk_size(self), &mut |block| self.encrypt(block))
+    }
+
+    /// Like [`PublicKey::encrypt_chunked`], but draws all randomness from `rng`.
+    fn encrypt_chunked_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>>
+    where
+        Self: Sized,
+    {
+        encrypt_chunks(bytes, chunk_size(self), &mut |block| {
+            self.encrypt_with_rng(block, rng)
+        })
+    }
+
+    /// Encrypts the slice and protects it against tampering.
+    ///
+    /// A random secret is encrypted with the public key and placed in front of the message.
+    /// The message itself is split into records that are encrypted and authenticated with
+    /// keys derived from that secret, each bound to its sequence number and, for the last
+    /// one, to the flag marking it as final.
+    fn encrypt_authenticated(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        let mut secret = Zeroizing::new([0u8; SECRET_SIZE]);
+        rand::thread_rng().fill_bytes(secret.as_mut());
+
+        encrypt_sealed(bytes, chunk_size(self), secret.as_ref(), &mut |block| {
+            self.encrypt(block)
+        })
+    }
+
+    /// Like [`PublicKey::encrypt_authenticated`], but draws the secret and all
+    /// other randomness from `rng`.
+    fn encrypt_authenticated_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>>
+    where
+        Self: Sized,
+    {
+        let mut secret = Zeroizing::new([0u8; SECRET_SIZE]);
+        rng.fill_bytes(secret.as_mut());
 
-        Ok(marshal_bytes(&content))
+        encrypt_sealed(bytes, chunk_size(self), secret.as_ref(), &mut |block| {
+            self.encrypt_with_rng(block, rng)
+        })
     }
 }
 
@@ -21,44 +206,170 @@ pub trait PrivateKey {
     /// Decrypts an encrypted slice using the public key.
     fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>>;
 
+    /// Returns the cryptosystem the key belongs to.
+    fn algorithm(&self) -> Algorithm;
+
     /// Unmarshalls the given slice containing chunks and then decrypts each separately using the public key.
-    fn decrypt_chunked(&self, message: &[u8], _chunk_size: usize) -> Result<Vec<u8>> {
-        let bytes: Vec<u8> = unmarshal_bytes(message)
-            .iter()
-            .flat_map(|chunk| self.decrypt(chunk))
-            .flatten()
-            .collect();
+    fn decrypt_chunked(&self, message: &[u8]) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(message.len());
+        for (index, chunk) in unmarshal_bytes(message)?.iter().enumerate() {
+            bytes.extend(decrypt_block(self, chunk).map_err(|e| e.at_chunk(index))?);
+        }
+
+        Ok(bytes)
+    }
+
+    /// Decrypts a message produced by [`PublicKey::encrypt_authenticated`].
+    ///
+    /// Fails with [`Error::Verification`] if any record was modified, sealed under
+    /// another secret, or is missing, duplicated, reordered or wrongly marked as final.
+    fn decrypt_authenticated(&self, message: &[u8]) -> Result<Vec<u8>> {
+        let content = unmarshal_bytes(message)?;
+        if content.len() < 2 {
+            return Err(Error::MalformedInput {
+                reason: "expected a secret and at least one record",
+                chunk: None,
+            });
+        }
+
+        let secret = Zeroizing::new(self.decrypt_chunked(&content[0])?);
+        if secret.len() != SECRET_SIZE {
+            return Err(Error::Verification {
+                algorithm: self.algorithm(),
+                chunk: None,
+            });
+        }
+
+        let keys = RecordKeys::derive(&secret);
+        let records = &content[1..];
+        let mut bytes = Vec::with_capacity(message.len());
+        for (sequence, record) in records.iter().enumerate() {
+            let is_final = sequence + 1 == records.len();
+            bytes.extend(keys.open(self.algorithm(), record, sequence, is_final)?);
+        }
 
         Ok(bytes)
     }
 }
 
 pub trait Signer {
-    fn sign(&self, data: &[u8], hashf: fn(&[u8]) -> Vec<u8>) -> Result<Vec<u8>>;
+    /// Signs an already computed hash of the message.
+    ///
+    /// Hashes wider than the key are truncated to their leftmost bits
+    /// before signing rather than reduced modulo the key.
+    fn sign_hash(&self, hash: &[u8]) -> Result<Vec<u8>>;
+
+    /// Signs an already computed hash, drawing any randomness the scheme needs from `rng`.
+    ///
+    /// Deterministic schemes ignore the generator and behave like [`Signer::sign_hash`].
+    #[inline]
+    fn sign_hash_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        hash: &[u8],
+        _rng: &mut R,
+    ) -> Result<Vec<u8>>
+    where
+        Self: Sized,
+    {
+        self.sign_hash(hash)
+    }
+
+    /// Hashes the data using `hashf` and signs the result.
+    #[inline]
+    fn sign(&self, data: &[u8], hashf: fn(&[u8]) -> Vec<u8>) -> Result<Vec<u8>> {
+        self.sign_hash(&hashf(data))
+    }
+
+    /// Like [`Signer::sign`], but draws all randomness from `rng`.
+    #[inline]
+    fn sign_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        data: &[u8],
+        hashf: fn(&[u8]) -> Vec<u8>,
+        rng: &mut R,
+    ) -> Result<Vec<u8>>
+    where
+        Self: Sized,
+    {
+        self.sign_hash_with_rng(&hashf(data), rng)
+    }
+
+    /// Finalizes an incrementally updated digest and signs the whole message with a single signature.
+    #[inline]
+    fn sign_digest<D: Digest>(&self, digest: D) -> Result<Vec<u8>>
+    where
+        Self: Sized,
+    {
+        self.sign_hash(&digest.finalize())
+    }
+
+    /// Like [`Signer::sign_digest`], but draws all randomness from `rng`.
+    #[inline]
+    fn sign_digest_with_rng<D: Digest, R: RngCore + CryptoRng>(
+        &self,
+        digest: D,
+        rng: &mut R,
+    ) -> Result<Vec<u8>>
+    where
+        Self: Sized,
+    {
+        self.sign_hash_with_rng(&digest.finalize(), rng)
+    }
 
+    /// Signs the data chunk by chunk, binding each chunk to its index and to the total length.
     fn sign_chunked(
         &self,
         data: &[u8],
         hashf: fn(&[u8]) -> Vec<u8>,
         chunk_size: usize,
     ) -> Result<Vec<u8>> {
-        Ok(marshal_bytes(
-            &data
-                .chunks(chunk_size)
-                .map(|chunk| self.sign(chunk, hashf))
-                .collect::<Result<_>>()?,
-        ))
+        sign_chunks(data, chunk_size, &mut |bound| self.sign(bound, hashf))
+    }
+
+    /// Like [`Signer::sign_chunked`], but draws all randomness from `rng`.
+    fn sign_chunked_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        data: &[u8],
+        hashf: fn(&[u8]) -> Vec<u8>,
+        chunk_size: usize,
+        rng: &mut R,
+    ) -> Result<Vec<u8>>
+    where
+        Self: Sized,
+    {
+        sign_chunks(data, chunk_size, &mut |bound| {
+            self.sign_with_rng(bound, hashf, rng)
+        })
     }
 }
 
 pub trait Verifier {
+    /// Verifies a signature over an already computed hash of the message.
+    fn verify_hash(&self, hash: &[u8], signed_data: &[u8]) -> Result<bool>;
+
+    /// Hashes the expected data using `hashf` and verifies the signature against the result.
+    #[inline]
     fn verify(
         &self,
         expected: &[u8],
         signed_data: &[u8],
         hashf: fn(&[u8]) -> Vec<u8>,
-    ) -> Result<bool>;
+    ) -> Result<bool> {
+        self.verify_hash(&hashf(expected), signed_data)
+    }
 
+    /// Finalizes an incrementally updated digest and verifies a signature produced by [`Signer::sign_digest`].
+    #[inline]
+    fn verify_digest<D: Digest>(&self, digest: D, signed_data: &[u8]) -> Result<bool>
+    where
+        Self: Sized,
+    {
+        self.verify_hash(&digest.finalize(), signed_data)
+    }
+
+    /// Verifies signatures produced by [`Signer::sign_chunked`].
+    ///
+    /// Returns `false` unless there is exactly one valid signature per chunk, in order.
     fn verify_chunked(
         &self,
         expected: &[u8],
@@ -66,10 +377,19 @@ pub trait Verifier {
         hashf: fn(&[u8]) -> Vec<u8>,
         chunk_size: usize,
     ) -> Result<bool> {
-        Ok(unmarshal_bytes(signed_data)
-            .iter()
-            .zip(expected.chunks(chunk_size))
-            .flat_map(|(sig, exp)| self.verify(exp, sig, hashf))
-            .all(|b| b))
+        let signatures = unmarshal_bytes(signed_data)?;
+        let chunks = signed_chunks(expected, chunk_size);
+        if signatures.len() != chunks.len() {
+            return Ok(false);
+        }
+
+        for (index, (sig, chunk)) in signatures.iter().zip(chunks).enumerate() {
+            let bound = bind_chunk(index, expected.len(), chunk);
+            if !self.verify(&bound, sig, hashf)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
     }
 }
@@ -1,10 +1,19 @@
+pub mod drbg;
 pub mod result;
+pub mod secret;
 mod utils;
 
+pub mod cramer_shoup;
+pub mod damgard_jurik;
 pub mod elgamal;
+pub mod goldwasser_micali;
+pub mod okamoto_uchiyama;
+pub mod paillier;
 pub mod rabin;
 pub mod rsa;
 
+mod homomorphic;
 mod keypair;
+pub use homomorphic::Homomorphic;
 pub use keypair::{PrivateKey, PublicKey};
 pub use keypair::{Signer, Verifier};
@@ -0,0 +1,24 @@
+use ibig::UBig;
+
+use crate::result::Result;
+use crate::utils::{tag_bytes, untag_bytes, TypeTag};
+
+/// A single block encrypted with a Okamoto–Uchiyama public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OkamotoUchiyamaCiphertext(pub UBig);
+
+impl OkamotoUchiyamaCiphertext {
+    /// Encodes the ciphertext as its type tag followed by little-endian bytes.
+    #[inline]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        tag_bytes(TypeTag::OkamotoUchiyamaCiphertext, &self.0.to_le_bytes())
+    }
+
+    /// Decodes a ciphertext produced by [`OkamotoUchiyamaCiphertext::to_bytes`].
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let bytes = untag_bytes(TypeTag::OkamotoUchiyamaCiphertext, bytes)?;
+        Ok(Self(UBig::from_le_bytes(bytes)))
+    }
+}
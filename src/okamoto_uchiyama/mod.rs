@@ -0,0 +1,12 @@
+mod ciphertext;
+mod pair;
+mod private;
+mod public;
+
+pub use ciphertext::OkamotoUchiyamaCiphertext;
+pub use pair::OkamotoUchiyamaKeyPair;
+pub use private::OkamotoUchiyamaPrivate;
+pub use public::OkamotoUchiyamaPublic;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,120 @@
+use ibig::{ubig, UBig};
+use rand::{CryptoRng, Rng, RngCore};
+
+use super::{OkamotoUchiyamaPrivate, OkamotoUchiyamaPublic};
+use crate::result::{Algorithm, Result};
+use crate::utils::{check_prime_bits, gen_prime, PowMod};
+use crate::{PrivateKey, PublicKey};
+
+/// A key pair for the Okamoto–Uchiyama cryptosystem.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OkamotoUchiyamaKeyPair {
+    /// The public key for encryption.
+    pub public: OkamotoUchiyamaPublic,
+
+    /// The private key for decryption.
+    pub private: OkamotoUchiyamaPrivate,
+}
+
+impl OkamotoUchiyamaKeyPair {
+    /// Generates a new Okamoto–Uchiyama key pair with the specified bit length and persistence level.
+    ///
+    /// * `bit_length`: The desired bit length of each of the two primes.
+    /// * `persistence`: The number of iterations for checking numbers for primality.
+    ///
+    /// Returns a newly generated `OkamotoUchiyamaKeyPair` instance, or
+    /// [`Error::KeyGeneration`](crate::result::Error::KeyGeneration) if `bit_length` is too small.
+    #[inline]
+    pub fn new(bit_length: usize, persistence: usize) -> Result<Self> {
+        Self::new_with_rng(bit_length, persistence, &mut rand::thread_rng())
+    }
+
+    /// Generates a new Okamoto–Uchiyama key pair, drawing all randomness from `rng`.
+    ///
+    /// The same seeded generator always yields the same key pair.
+    pub fn new_with_rng<R: RngCore + CryptoRng>(
+        bit_length: usize,
+        persistence: usize,
+        rng: &mut R,
+    ) -> Result<Self> {
+        check_prime_bits(Algorithm::OkamotoUchiyama, bit_length)?;
+
+        let p = gen_prime(bit_length, persistence, rng, |_| true);
+        let q = loop {
+            let q: UBig = gen_prime(bit_length, persistence, rng, |_| true);
+            if q != p {
+                break q;
+            }
+        };
+
+        let square = &p * &p;
+        let divisor = &square * &q;
+
+        // The generator must have an order divisible by p modulo p ^ 2, or decryption cannot
+        // recover anything; almost every random element qualifies.
+        let generator = loop {
+            let g = rng.gen_range(ubig!(2)..divisor.clone());
+            if g.powmod(&p - 1u8, &square) != ubig!(1) {
+                break g;
+            }
+        };
+        let mask_base = generator.powmod(divisor.clone(), &divisor);
+
+        Ok(Self {
+            public: OkamotoUchiyamaPublic {
+                divisor,
+                generator: generator.clone(),
+                mask_base,
+            },
+            private: OkamotoUchiyamaPrivate {
+                prime_1: p.into(),
+                prime_2: q.into(),
+                generator,
+            },
+        })
+    }
+}
+
+impl PrivateKey for OkamotoUchiyamaKeyPair {
+    #[inline]
+    fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.private.decrypt(message)
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::OkamotoUchiyama
+    }
+}
+
+impl PublicKey for OkamotoUchiyamaKeyPair {
+    #[inline]
+    fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        self.public.encrypt(bytes)
+    }
+
+    #[inline]
+    fn encrypt_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        self.public.encrypt_with_rng(bytes, rng)
+    }
+
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.public.bit_length()
+    }
+
+    #[inline]
+    fn max_message_size(&self) -> usize {
+        self.public.max_message_size()
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::OkamotoUchiyama
+    }
+}
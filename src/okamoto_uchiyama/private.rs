@@ -0,0 +1,73 @@
+use ibig::{ubig, UBig};
+
+use super::OkamotoUchiyamaCiphertext;
+use crate::result::{Algorithm, Error};
+use crate::secret::SecretUBig;
+use crate::utils::{ct_powmod, imod};
+use crate::{keypair::PrivateKey, result::Result};
+
+/// Private key for the Okamoto–Uchiyama cryptosystem.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OkamotoUchiyamaPrivate {
+    /// The prime `p` whose square divides the public modulus.
+    pub prime_1: SecretUBig,
+
+    /// The prime `q` that divides the public modulus once.
+    pub prime_2: SecretUBig,
+
+    /// The public generator `g`.
+    pub generator: UBig,
+}
+
+impl OkamotoUchiyamaPrivate {
+    /// Decrypts a single typed ciphertext block.
+    ///
+    /// The plaintext is recovered as `L(c ^ (p - 1) mod p ^ 2) / L(g ^ (p - 1) mod p ^ 2) mod p`,
+    /// where `L(x) = (x - 1) / p`.
+    pub fn decrypt_block(&self, ciphertext: &OkamotoUchiyamaCiphertext) -> Result<Vec<u8>> {
+        let p = self.prime_1.expose();
+        let square = p * p;
+
+        if ciphertext.0 >= &square * self.prime_2.expose() {
+            return Err(Error::OutOfRange {
+                algorithm: Algorithm::OkamotoUchiyama,
+                reason: "ciphertext is not smaller than the modulus",
+            });
+        }
+
+        if &ciphertext.0 % p == ubig!(0) {
+            return Err(Error::OutOfRange {
+                algorithm: Algorithm::OkamotoUchiyama,
+                reason: "ciphertext is not invertible",
+            });
+        }
+
+        let exponent = p - 1u8;
+        let l = |x: UBig| (x - 1u8) / p;
+
+        let (gcd, h, _) =
+            l(ct_powmod(&(&self.generator % &square), &exponent, &square)).extended_gcd(p);
+        if gcd != ubig!(1) {
+            return Err(Error::InvalidKey {
+                algorithm: Algorithm::OkamotoUchiyama,
+                reason: "generator has an order not divisible by the prime",
+            });
+        }
+
+        let c = ct_powmod(&(&ciphertext.0 % &square), &exponent, &square);
+        Ok(((l(c) * imod(&h, p)) % p).to_le_bytes())
+    }
+}
+
+impl PrivateKey for OkamotoUchiyamaPrivate {
+    #[inline]
+    fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.decrypt_block(&OkamotoUchiyamaCiphertext::from_bytes(message)?)
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::OkamotoUchiyama
+    }
+}
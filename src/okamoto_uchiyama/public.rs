@@ -0,0 +1,191 @@
+use ibig::{ubig, UBig};
+use rand::{CryptoRng, Rng, RngCore};
+
+use super::OkamotoUchiyamaCiphertext;
+use crate::homomorphic::Homomorphic;
+use crate::result::{Algorithm, Error};
+use crate::utils::{has_small_factor, PowMod, MIN_MODULUS_BITS};
+use crate::{keypair::PublicKey, result::Result};
+
+/// Public key for the Okamoto–Uchiyama cryptosystem.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OkamotoUchiyamaPublic {
+    /// Public modulus `n = p ^ 2 * q`.
+    pub divisor: UBig,
+
+    /// Generator `g` whose order modulo `p ^ 2` is divisible by `p`.
+    pub generator: UBig,
+
+    /// Masking base `h = g ^ n mod n`.
+    pub mask_base: UBig,
+}
+
+impl OkamotoUchiyamaPublic {
+    /// Checks that the key is plausible before it is used.
+    ///
+    /// The modulus must be odd, at least `MIN_MODULUS_BITS` wide and free of small factors,
+    /// and both the generator and the masking base must lie strictly between `1` and `n`.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |reason| {
+            Err(Error::InvalidKey {
+                algorithm: Algorithm::OkamotoUchiyama,
+                reason,
+            })
+        };
+
+        if self.divisor.bit_len() < MIN_MODULUS_BITS {
+            return invalid("modulus is too small");
+        }
+
+        if &self.divisor % 2u8 == 0u8 {
+            return invalid("modulus is even");
+        }
+
+        if has_small_factor(&self.divisor) {
+            return invalid("modulus has a small factor");
+        }
+
+        if self.generator <= ubig!(1) || self.generator >= self.divisor {
+            return invalid("generator is out of range");
+        }
+
+        if self.mask_base <= ubig!(1) || self.mask_base >= self.divisor {
+            return invalid("masking base is out of range");
+        }
+
+        Ok(())
+    }
+
+    /// Ensures that the ciphertext lies below `n`.
+    fn check_ciphertext(&self, ciphertext: &OkamotoUchiyamaCiphertext) -> Result<()> {
+        match ciphertext.0 < self.divisor {
+            true => Ok(()),
+            false => Err(Error::OutOfRange {
+                algorithm: Algorithm::OkamotoUchiyama,
+                reason: "ciphertext is not smaller than the modulus",
+            }),
+        }
+    }
+
+    /// Draws a random `h ^ r mod n`, an encryption of zero.
+    fn random_mask<R: RngCore + CryptoRng>(&self, rng: &mut R) -> UBig {
+        let r = rng.gen_range(ubig!(1)..self.divisor.clone());
+        self.mask_base.powmod(r, &self.divisor)
+    }
+
+    /// Encrypts a single block into a typed ciphertext.
+    #[inline]
+    pub fn encrypt_block(&self, bytes: &[u8]) -> Result<OkamotoUchiyamaCiphertext> {
+        self.encrypt_block_with_rng(bytes, &mut rand::thread_rng())
+    }
+
+    /// Encrypts a single block into a typed ciphertext, drawing the randomness from `rng`.
+    ///
+    /// The plaintext must stay below the secret prime `p`, so it is limited to one bit
+    /// less than a third of the modulus.
+    pub fn encrypt_block_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<OkamotoUchiyamaCiphertext> {
+        let message = UBig::from_le_bytes(bytes);
+        if message.bit_len() >= self.divisor.bit_len() / 3 {
+            return Err(Error::MessageTooLarge {
+                algorithm: Algorithm::OkamotoUchiyama,
+                max: self.max_message_size(),
+                actual: bytes.len(),
+            });
+        }
+
+        let shifted = self.generator.powmod(message, &self.divisor);
+        Ok(OkamotoUchiyamaCiphertext(
+            (shifted * self.random_mask(rng)) % &self.divisor,
+        ))
+    }
+
+    /// Computes an encryption of the sum of both plaintexts modulo `p`.
+    #[inline]
+    pub fn add(
+        &self,
+        a: &OkamotoUchiyamaCiphertext,
+        b: &OkamotoUchiyamaCiphertext,
+    ) -> Result<OkamotoUchiyamaCiphertext> {
+        self.mul(a, b)
+    }
+}
+
+/// Okamoto–Uchiyama is additively homomorphic: [`Homomorphic::mul`] adds the plaintexts
+/// modulo `p` and [`Homomorphic::pow`] multiplies the plaintext by a scalar.
+impl Homomorphic for OkamotoUchiyamaPublic {
+    type Ciphertext = OkamotoUchiyamaCiphertext;
+
+    fn mul(
+        &self,
+        a: &OkamotoUchiyamaCiphertext,
+        b: &OkamotoUchiyamaCiphertext,
+    ) -> Result<OkamotoUchiyamaCiphertext> {
+        self.check_ciphertext(a)?;
+        self.check_ciphertext(b)?;
+
+        Ok(OkamotoUchiyamaCiphertext((&a.0 * &b.0) % &self.divisor))
+    }
+
+    fn pow(
+        &self,
+        ciphertext: &OkamotoUchiyamaCiphertext,
+        exponent: &UBig,
+    ) -> Result<OkamotoUchiyamaCiphertext> {
+        self.check_ciphertext(ciphertext)?;
+
+        Ok(OkamotoUchiyamaCiphertext(
+            ciphertext.0.powmod(exponent.clone(), &self.divisor),
+        ))
+    }
+
+    /// Multiplies the ciphertext with a fresh `h ^ r`, an encryption of zero.
+    fn rerandomize_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        ciphertext: &OkamotoUchiyamaCiphertext,
+        rng: &mut R,
+    ) -> Result<OkamotoUchiyamaCiphertext> {
+        self.check_ciphertext(ciphertext)?;
+
+        let mask = self.random_mask(rng);
+        Ok(OkamotoUchiyamaCiphertext(
+            (&ciphertext.0 * mask) % &self.divisor,
+        ))
+    }
+}
+
+impl PublicKey for OkamotoUchiyamaPublic {
+    #[inline]
+    fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        self.encrypt_block(bytes).map(|c| c.to_bytes())
+    }
+
+    #[inline]
+    fn encrypt_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        self.encrypt_block_with_rng(bytes, rng)
+            .map(|c| c.to_bytes())
+    }
+
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.divisor.bit_len()
+    }
+
+    #[inline]
+    fn max_message_size(&self) -> usize {
+        (self.divisor.bit_len() / 3).saturating_sub(1) / 8
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::OkamotoUchiyama
+    }
+}
@@ -0,0 +1,12 @@
+mod ciphertext;
+mod pair;
+mod private;
+mod public;
+
+pub use ciphertext::PaillierCiphertext;
+pub use pair::PaillierKeyPair;
+pub use private::PaillierPrivate;
+pub use public::PaillierPublic;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,105 @@
+use rand::{CryptoRng, RngCore};
+
+use super::private::generator_inverse;
+use super::{PaillierPrivate, PaillierPublic};
+use crate::result::{Algorithm, Result};
+use crate::utils::{check_prime_bits, gen_prime_pair, imod};
+use crate::{PrivateKey, PublicKey};
+
+/// A key pair for the Paillier cryptosystem.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PaillierKeyPair {
+    /// The public key for encryption.
+    pub public: PaillierPublic,
+
+    /// The private key for decryption.
+    pub private: PaillierPrivate,
+}
+
+impl PaillierKeyPair {
+    /// Generates a new Paillier key pair with the specified bit length and persistence level.
+    ///
+    /// * `bit_length`: The desired bit length of each of the two primes.
+    /// * `persistence`: The number of iterations for checking numbers for primality.
+    ///
+    /// Returns a newly generated `PaillierKeyPair` instance, or
+    /// [`Error::KeyGeneration`](crate::result::Error::KeyGeneration) if `bit_length` is too small.
+    #[inline]
+    pub fn new(bit_length: usize, persistence: usize) -> Result<Self> {
+        Self::new_with_rng(bit_length, persistence, &mut rand::thread_rng())
+    }
+
+    /// Generates a new Paillier key pair, drawing all randomness from `rng`.
+    ///
+    /// The same seeded generator always yields the same key pair.
+    pub fn new_with_rng<R: RngCore + CryptoRng>(
+        bit_length: usize,
+        persistence: usize,
+        rng: &mut R,
+    ) -> Result<Self> {
+        check_prime_bits(Algorithm::Paillier, bit_length)?;
+
+        let (p, q) = gen_prime_pair(bit_length, persistence, rng);
+        let n = &p * &q;
+
+        let hp = generator_inverse(&p, &n);
+        let hq = generator_inverse(&q, &n);
+        let (_, coefficient, _) = p.extended_gcd(&q);
+        let coefficient = imod(&coefficient, &q);
+
+        Ok(Self {
+            public: PaillierPublic { divisor: n },
+            private: PaillierPrivate {
+                prime_1: p.into(),
+                prime_2: q.into(),
+                hp: hp.into(),
+                hq: hq.into(),
+                coefficient: coefficient.into(),
+            },
+        })
+    }
+}
+
+impl PrivateKey for PaillierKeyPair {
+    #[inline]
+    fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.private.decrypt(message)
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Paillier
+    }
+}
+
+impl PublicKey for PaillierKeyPair {
+    #[inline]
+    fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        self.public.encrypt(bytes)
+    }
+
+    #[inline]
+    fn encrypt_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        self.public.encrypt_with_rng(bytes, rng)
+    }
+
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.public.bit_length()
+    }
+
+    #[inline]
+    fn max_message_size(&self) -> usize {
+        self.public.max_message_size()
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Paillier
+    }
+}
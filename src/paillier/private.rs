@@ -0,0 +1,96 @@
+use ibig::{ubig, UBig};
+
+use super::PaillierCiphertext;
+use crate::result::{Algorithm, Error};
+use crate::secret::SecretUBig;
+use crate::utils::{ct_powmod, imod, mod_sub};
+use crate::{keypair::PrivateKey, result::Result};
+
+/// Private key for the Paillier cryptosystem.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PaillierPrivate {
+    /// First prime factor of the public modulus.
+    pub prime_1: SecretUBig,
+
+    /// Second prime factor of the public modulus.
+    pub prime_2: SecretUBig,
+
+    /// The inverse of `L(g ^ (p - 1) mod p ^ 2)` modulo `prime_1`.
+    pub hp: SecretUBig,
+
+    /// The inverse of `L(g ^ (q - 1) mod q ^ 2)` modulo `prime_2`.
+    pub hq: SecretUBig,
+
+    /// The inverse of `prime_1` modulo `prime_2`, which recombines values
+    /// known modulo each prime.
+    pub coefficient: SecretUBig,
+}
+
+impl PaillierPrivate {
+    /// Decrypts a single typed ciphertext block.
+    ///
+    /// The plaintext is recovered modulo each prime separately and recombined
+    /// with the Chinese remainder theorem, which needs exponentiations modulo
+    /// `p ^ 2` and `q ^ 2` only instead of one modulo `n ^ 2`.
+    pub fn decrypt_block(&self, ciphertext: &PaillierCiphertext) -> Result<Vec<u8>> {
+        let p = self.prime_1.expose();
+        let q = self.prime_2.expose();
+
+        let n = p * q;
+        if ciphertext.0 >= &n * &n {
+            return Err(Error::OutOfRange {
+                algorithm: Algorithm::Paillier,
+                reason: "ciphertext is not smaller than the squared modulus",
+            });
+        }
+
+        let mp = decrypt_modulo(&ciphertext.0, p, self.hp.expose())?;
+        let mq = decrypt_modulo(&ciphertext.0, q, self.hq.expose())?;
+
+        let h = (mod_sub(&mq, &mp, q) * self.coefficient.expose()) % q;
+
+        Ok((mp + h * p).to_le_bytes())
+    }
+}
+
+/// Returns the inverse of `L(g ^ (p - 1) mod p ^ 2)` modulo the prime `p`, with
+/// `g = n + 1` and `L(x) = (x - 1) / p`.
+///
+/// The value is `(p - 1) * n / p mod p`, which is invertible whenever the
+/// two primes differ.
+pub(super) fn generator_inverse(p: &UBig, n: &UBig) -> UBig {
+    let square = p * p;
+    let generator = (n + 1u8) % &square;
+    let power = ct_powmod(&generator, &(p - 1u8), &square);
+
+    let (_, inverse, _) = ((power - 1u8) / p).extended_gcd(p);
+    imod(&inverse, p)
+}
+
+/// Recovers the plaintext modulo the prime `p` as `L(c ^ (p - 1) mod p ^ 2) * h mod p`,
+/// where `L(x) = (x - 1) / p` and `h` is [`generator_inverse`].
+fn decrypt_modulo(ciphertext: &UBig, p: &UBig, h: &UBig) -> Result<UBig> {
+    if ciphertext % p == ubig!(0) {
+        return Err(Error::OutOfRange {
+            algorithm: Algorithm::Paillier,
+            reason: "ciphertext is not invertible",
+        });
+    }
+
+    let square = p * p;
+    let c = ct_powmod(&(ciphertext % &square), &(p - 1u8), &square);
+    Ok((((c - 1u8) / p) * h) % p)
+}
+
+impl PrivateKey for PaillierPrivate {
+    #[inline]
+    fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.decrypt_block(&PaillierCiphertext::from_bytes(message)?)
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Paillier
+    }
+}
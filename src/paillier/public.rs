@@ -0,0 +1,125 @@
+use ibig::UBig;
+use rand::{CryptoRng, RngCore};
+
+use super::PaillierCiphertext;
+use crate::homomorphic::Homomorphic;
+use crate::result::{Algorithm, Error};
+use crate::utils::CiphertextSpace;
+use crate::{keypair::PublicKey, result::Result};
+
+/// Public key for the Paillier cryptosystem.
+///
+/// The generator is fixed to `n + 1`, so the modulus is all the key consists of.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PaillierPublic {
+    /// Public modulus `n`, the product of two primes of equal length.
+    pub divisor: UBig,
+}
+
+impl PaillierPublic {
+    /// Checks that the key is plausible before it is used.
+    ///
+    /// The modulus must be odd, at least `MIN_MODULUS_BITS` wide and free of small factors.
+    #[inline]
+    pub fn validate(&self) -> Result<()> {
+        self.space().validate()
+    }
+
+    /// Returns the ciphertext space of the key, that of Damgård–Jurik with `s = 1`.
+    #[inline]
+    fn space(&self) -> CiphertextSpace<'_> {
+        CiphertextSpace {
+            algorithm: Algorithm::Paillier,
+            divisor: &self.divisor,
+            degree: 1,
+        }
+    }
+
+    /// Encrypts a single block into a typed ciphertext.
+    #[inline]
+    pub fn encrypt_block(&self, bytes: &[u8]) -> Result<PaillierCiphertext> {
+        self.encrypt_block_with_rng(bytes, &mut rand::thread_rng())
+    }
+
+    /// Encrypts a single block into a typed ciphertext, drawing the randomness from `rng`.
+    pub fn encrypt_block_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<PaillierCiphertext> {
+        let message = UBig::from_le_bytes(bytes);
+        if message >= self.divisor {
+            return Err(Error::MessageTooLarge {
+                algorithm: Algorithm::Paillier,
+                max: self.max_message_size(),
+                actual: bytes.len(),
+            });
+        }
+
+        // With g = n + 1, g ^ m mod n ^ 2 is simply 1 + m * n.
+        let space = self.space();
+        let square = space.ciphertext_modulus();
+        let shifted = (message * &self.divisor + 1u8) % &square;
+        Ok(PaillierCiphertext(
+            (shifted * space.random_mask(rng)) % square,
+        ))
+    }
+}
+
+/// Paillier is additively homomorphic: [`Homomorphic::mul`] adds the plaintexts modulo `n`
+/// and [`Homomorphic::pow`] multiplies the plaintext by a scalar.
+impl Homomorphic for PaillierPublic {
+    type Ciphertext = PaillierCiphertext;
+
+    #[inline]
+    fn mul(&self, a: &PaillierCiphertext, b: &PaillierCiphertext) -> Result<PaillierCiphertext> {
+        self.space().mul(&a.0, &b.0).map(PaillierCiphertext)
+    }
+
+    #[inline]
+    fn pow(&self, ciphertext: &PaillierCiphertext, exponent: &UBig) -> Result<PaillierCiphertext> {
+        self.space()
+            .pow(&ciphertext.0, exponent)
+            .map(PaillierCiphertext)
+    }
+
+    /// Multiplies the ciphertext with a fresh `r ^ n`, an encryption of zero.
+    #[inline]
+    fn rerandomize_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        ciphertext: &PaillierCiphertext,
+        rng: &mut R,
+    ) -> Result<PaillierCiphertext> {
+        self.space()
+            .rerandomize(&ciphertext.0, rng)
+            .map(PaillierCiphertext)
+    }
+}
+
+impl PublicKey for PaillierPublic {
+    #[inline]
+    fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        self.encrypt_block(bytes).map(|c| c.to_bytes())
+    }
+
+    #[inline]
+    fn encrypt_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        self.encrypt_block_with_rng(bytes, rng)
+            .map(|c| c.to_bytes())
+    }
+
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.divisor.bit_len()
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Paillier
+    }
+}
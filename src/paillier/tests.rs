@@ -0,0 +1,49 @@
+use ibig::UBig;
+use rand::{rngs::StdRng, SeedableRng};
+
+use super::{PaillierCiphertext, PaillierKeyPair};
+use crate::keypair::{PrivateKey, PublicKey};
+use crate::result::Error;
+use crate::Homomorphic;
+
+const MESSAGE: [u8; 445] = *b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.";
+
+#[test]
+fn test_encrypt_decrypt() {
+    let key = PaillierKeyPair::new(128, 5).unwrap();
+
+    let encrypted = key.encrypt_chunked(&MESSAGE).unwrap();
+    let decrypted = key.decrypt_chunked(&encrypted).unwrap();
+    assert_eq!(MESSAGE, decrypted.as_slice());
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let first = key.encrypt_with_rng(b"Lorem", &mut rng).unwrap();
+    let second = key.encrypt_with_rng(b"Lorem", &mut rng).unwrap();
+    assert_ne!(first, second);
+}
+
+#[test]
+fn test_homomorphic_operations() {
+    let key = PaillierKeyPair::new(128, 5).unwrap();
+    let decrypt =
+        |c: &PaillierCiphertext| UBig::from_le_bytes(&key.private.decrypt_block(c).unwrap());
+
+    let ca = key.public.encrypt_block(&[200]).unwrap();
+    let cb = key.public.encrypt_block(&[100]).unwrap();
+
+    let sum = key.public.mul(&ca, &cb).unwrap();
+    assert_eq!(decrypt(&sum), UBig::from(300u16));
+
+    let scaled = key.public.pow(&ca, &UBig::from(1000u16)).unwrap();
+    assert_eq!(decrypt(&scaled), UBig::from(200_000u32));
+
+    let fresh = key.public.rerandomize(&ca).unwrap();
+    assert_ne!(fresh, ca);
+    assert_eq!(decrypt(&fresh), UBig::from(200u8));
+
+    let n = &key.public.divisor;
+    assert!(matches!(
+        key.private.decrypt_block(&PaillierCiphertext(n * n)),
+        Err(Error::OutOfRange { .. })
+    ));
+}
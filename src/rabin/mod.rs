@@ -1,13 +1,45 @@
+mod ciphertext;
 mod pair;
 mod private;
 mod public;
+mod signature;
 
-/// This constant value (`MAGIC`) is used to pad encrypted messages
-/// with the Rabin cryptosystem. This padding helps identify the
-/// original message among the four possible decryption candidates
-/// returned due to Rabin's inherent ambiguity.
+use ibig::UBig;
+use sha2::{Digest, Sha256};
+
+/// This constant value (`MAGIC`) is appended above the most significant byte
+/// of every message encrypted with the Rabin cryptosystem. Only the correct one
+/// of the four square roots found during decryption carries it at the top,
+/// and where it starts marks where the message ends.
 pub(crate) const MAGIC: &[u8; 8] = b"\x00RABIN\x00\x01";
 
+pub use ciphertext::RabinCiphertext;
 pub use pair::RabinKeyPair;
 pub use private::RabinPrivate;
 pub use public::RabinPublic;
+pub use signature::RabinSignature;
+
+/// Expands a hash into a full-domain representative modulo `n`.
+///
+/// The output blocks `SHA-256(hash || counter)`, with a big-endian 32-bit counter, are
+/// concatenated until they are 16 bytes wider than `n`, read as a big-endian number and
+/// reduced modulo `n`, which leaves a bias too small to matter.
+fn full_domain_hash(hash: &[u8], n: &UBig) -> UBig {
+    let length = n.bit_len().div_ceil(8) + 16;
+
+    let mut expanded = Vec::with_capacity(length + 32);
+    let mut counter = 0u32;
+    while expanded.len() < length {
+        let mut hasher = Sha256::new();
+        hasher.update(hash);
+        hasher.update(counter.to_be_bytes());
+        expanded.extend(hasher.finalize());
+        counter += 1;
+    }
+
+    expanded.truncate(length);
+    UBig::from_be_bytes(&expanded) % n
+}
+
+#[cfg(test)]
+mod tests;
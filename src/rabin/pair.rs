@@ -1,8 +1,11 @@
 use ibig::UBig;
-use ibig_ext::prime_gen::gen_sized_prime;
+use rand::{CryptoRng, RngCore};
 
 use super::{private::RabinPrivate, public::RabinPublic};
-use crate::{result::Result, PrivateKey, PublicKey};
+use crate::result::{Algorithm, Result};
+use crate::utils::{check_prime_bits, gen_prime, imod};
+use crate::{PrivateKey, PublicKey};
+use crate::{Signer, Verifier};
 
 /// A key pair for the Rabin cryptosystem.
 #[derive(Debug, Clone)]
@@ -21,22 +24,42 @@ impl RabinKeyPair {
     /// * `bit_length`: The desired bit length for the keys in the pair (increased by 8 for internal logic).
     /// * `persistence`: The number of iterations for checking numbers for primality.
     ///
-    /// Returns a newly generated `RabinKeyPair` instance.
+    /// Returns a newly generated `RabinKeyPair` instance, or
+    /// [`Error::KeyGeneration`](crate::result::Error::KeyGeneration) if `bit_length` is too small.
     #[inline]
-    pub fn new(mut bit_length: usize, persistence: usize) -> Self {
+    pub fn new(bit_length: usize, persistence: usize) -> Result<Self> {
+        Self::new_with_rng(bit_length, persistence, &mut rand::thread_rng())
+    }
+
+    /// Generates a new Rabin key pair, drawing all randomness from `rng`.
+    ///
+    /// The same seeded generator always yields the same key pair.
+    pub fn new_with_rng<R: RngCore + CryptoRng>(
+        mut bit_length: usize,
+        persistence: usize,
+        rng: &mut R,
+    ) -> Result<Self> {
+        check_prime_bits(Algorithm::Rabin, bit_length)?;
+
         bit_length += 8;
 
-        let p = gen_prime(bit_length, persistence);
-        let q = gen_prime(bit_length, persistence);
+        // Both primes are Blum primes, as decryption needs, and the residues modulo 8
+        // are those Rabin–Williams signatures need. They also keep the primes distinct.
+        let p = gen_prime(bit_length, persistence, rng, |p: &UBig| p % 8u8 == 3u8);
+        let q = gen_prime(bit_length, persistence, rng, |q: &UBig| q % 8u8 == 7u8);
         let n = &p * &q;
 
-        Self {
+        let (_, coefficient, _) = p.extended_gcd(&q);
+        let coefficient = imod(&coefficient, &q);
+
+        Ok(Self {
             public: RabinPublic { divisor: n },
             private: RabinPrivate {
-                prime_1: p,
-                prime_2: q,
+                prime_1: p.into(),
+                prime_2: q.into(),
+                coefficient: coefficient.into(),
             },
-        }
+        })
     }
 }
 
@@ -45,6 +68,11 @@ impl PrivateKey for RabinKeyPair {
     fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
         self.private.decrypt(message)
     }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Rabin
+    }
 }
 
 impl PublicKey for RabinKeyPair {
@@ -52,13 +80,33 @@ impl PublicKey for RabinKeyPair {
     fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
         self.public.encrypt(bytes)
     }
+
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.public.bit_length()
+    }
+
+    #[inline]
+    fn max_message_size(&self) -> usize {
+        self.public.max_message_size()
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Rabin
+    }
 }
 
-fn gen_prime(byte_length: usize, persistence: usize) -> UBig {
-    loop {
-        let p = gen_sized_prime(byte_length, persistence);
-        if &p % 4 == 3 {
-            break p;
-        }
+impl Verifier for RabinKeyPair {
+    #[inline]
+    fn verify_hash(&self, hash: &[u8], signed_data: &[u8]) -> Result<bool> {
+        self.public.verify_hash(hash, signed_data)
+    }
+}
+
+impl Signer for RabinKeyPair {
+    #[inline]
+    fn sign_hash(&self, hash: &[u8]) -> Result<Vec<u8>> {
+        self.private.sign_hash(hash)
     }
 }
@@ -1,9 +1,10 @@
 use ibig::UBig;
-use ibig_ext::sqrt::SquareRootMod;
 
-use super::MAGIC;
-use crate::result::Error;
-use crate::utils::imod;
+use super::{full_domain_hash, RabinCiphertext, RabinSignature, MAGIC};
+use crate::keypair::Signer;
+use crate::result::{Algorithm, Error};
+use crate::secret::SecretUBig;
+use crate::utils::{ct_eq, ct_powmod, ct_select, mod_sub, to_fixed_le_bytes};
 use crate::{keypair::PrivateKey, result::Result};
 
 /// Private key for the Rabin cryptosystem.
@@ -11,42 +12,201 @@ use crate::{keypair::PrivateKey, result::Result};
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RabinPrivate {
     /// First prime factor of the public modulus.
-    pub prime_1: UBig,
+    pub prime_1: SecretUBig,
 
     /// Second prime factor of the public modulus.
-    pub prime_2: UBig,
+    pub prime_2: SecretUBig,
+
+    /// The inverse of `prime_1` modulo `prime_2`, which recombines values
+    /// known modulo each prime.
+    pub coefficient: SecretUBig,
 }
 
-impl PrivateKey for RabinPrivate {
-    #[inline]
-    fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
-        let p1 = &self.prime_1;
-        let p2 = &self.prime_2;
+impl RabinPrivate {
+    /// Decrypts a single typed ciphertext block.
+    ///
+    /// The square roots modulo both primes are combined into the four square roots
+    /// modulo `n`, and the one whose top bytes carry `MAGIC` is the plaintext.
+    pub fn decrypt_block(&self, ciphertext: &RabinCiphertext) -> Result<Vec<u8>> {
+        let p1 = self.prime_1.expose();
+        let p2 = self.prime_2.expose();
 
-        let (_, u, v) = p1.extended_gcd(p2);
-        let message = UBig::from_le_bytes(message);
-        let u = imod(&u, p2);
-        let v = imod(&v, p2);
+        let n = p1 * p2;
+        let message = &ciphertext.0;
+        if *message >= n {
+            return Err(Error::OutOfRange {
+                algorithm: Algorithm::Rabin,
+                reason: "ciphertext is not smaller than the modulus",
+            });
+        }
 
-        let mp1: UBig = message.clone().square_root_mod(p1).expect("No root").0;
-        let mp2: UBig = message.square_root_mod(p2).expect("No root").0;
+        // The square roots modulo n combine either root modulo p1 with either
+        // root modulo p2, and the other two are their negations.
+        let mp1 = square_root_mod(message, p1)?;
+        let mp2 = square_root_mod(message, p2)?;
 
-        let n = p1 * p2;
-        let x1 = &u * p1 * &mp2;
-        let x2 = &v * p2 * &mp1;
+        let m1 = self.combine(&mp1, &mp2);
+        let m2 = mod_sub(&n, &m1, &n);
+        let m3 = self.combine(&mp1, &mod_sub(p2, &mp2, p2));
+        let m4 = mod_sub(&n, &m3, &n);
 
-        let m1: UBig = (&x1 + &x2) % &n;
-        let m2: UBig = &n - &m1;
-        let m3: UBig = (&x1 - &x2) % &n;
-        let m4: UBig = n - &m3;
+        // Every candidate is inspected in full and the first one carrying
+        // `MAGIC` is picked without branching on which one it was.
+        let width = n.bit_len().div_ceil(8);
+        let mut selected = vec![0; width];
+        let mut length = 0;
+        let mut found = false;
 
         for m in [m1, m2, m3, m4] {
-            let m = m.to_le_bytes();
-            if m.ends_with(MAGIC) {
-                return Ok(m);
+            let m = to_fixed_le_bytes(&m, width);
+            let (valid, position) = find_magic(&m);
+
+            let choice = valid & !found;
+            selected = ct_select(&m, &selected, choice);
+            length ^= (choice as usize).wrapping_neg() & (length ^ position);
+            found |= valid;
+        }
+
+        match found {
+            true => {
+                selected.truncate(length);
+                Ok(selected)
             }
+            false => Err(Error::Padding {
+                algorithm: Algorithm::Rabin,
+                chunk: None,
+            }),
         }
+    }
 
-        Err(Error::MessageNotFound)
+    /// Signs an already computed hash of the message with the Rabin–Williams scheme.
+    ///
+    /// The hash is expanded into a full-domain representative `h`. As `p ≡ 3 (mod 8)` and
+    /// `q ≡ 7 (mod 8)`, exactly one choice of `e` in `{1, -1}` and `f` in `{1, 2}` turns
+    /// `h / (e * f)` into a square modulo both primes, and its principal square root,
+    /// the one that is itself a square, is the signature.
+    pub fn create_signature(&self, hash: &[u8]) -> Result<RabinSignature> {
+        let p = self.prime_1.expose();
+        let q = self.prime_2.expose();
+        if p % 8u8 != 3u8 || q % 8u8 != 7u8 {
+            return Err(Error::InvalidKey {
+                algorithm: Algorithm::Rabin,
+                reason: "primes are not congruent to 3 and 7 modulo 8",
+            });
+        }
+
+        let n = p * q;
+        let h = full_domain_hash(hash, &n);
+
+        // -1 is a non-residue modulo q while 2 is a residue, so e alone settles q.
+        let e: i8 = match is_non_residue(&h, q) {
+            true => -1,
+            false => 1,
+        };
+        let h = match e {
+            -1 => mod_sub(&n, &h, &n),
+            _ => h,
+        };
+
+        // Both -1 and 2 are non-residues modulo p, so f settles p without undoing e.
+        let f: u8 = match is_non_residue(&h, p) {
+            true => 2,
+            false => 1,
+        };
+        let square = match f {
+            2 => (h * ((&n + 1u8) >> 1)) % &n,
+            _ => h,
+        };
+
+        let root_p = square_root_mod(&square, p)?;
+        let root_q = square_root_mod(&square, q)?;
+
+        Ok(RabinSignature {
+            e,
+            f,
+            s: self.combine(&root_p, &root_q),
+        })
     }
+
+    /// Returns the value modulo `n` that is `x1` modulo `prime_1` and `x2` modulo `prime_2`.
+    fn combine(&self, x1: &UBig, x2: &UBig) -> UBig {
+        let p1 = self.prime_1.expose();
+        let p2 = self.prime_2.expose();
+
+        let lift = (mod_sub(x2, x1, p2) * self.coefficient.expose()) % p2;
+        x1 + lift * p1
+    }
+}
+
+impl PrivateKey for RabinPrivate {
+    #[inline]
+    fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.decrypt_block(&RabinCiphertext::from_bytes(message)?)
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Rabin
+    }
+}
+
+impl Signer for RabinPrivate {
+    #[inline]
+    fn sign_hash(&self, hash: &[u8]) -> Result<Vec<u8>> {
+        let signature = self.create_signature(hash)?;
+        let width = (self.prime_1.expose() * self.prime_2.expose())
+            .bit_len()
+            .div_ceil(8);
+
+        Ok(signature.to_fixed_bytes(width))
+    }
+}
+
+/// Computes a square root of `value` modulo a prime `p ≡ 3 (mod 4)`.
+///
+/// The exponent `(p + 1) / 4` is derived from the secret prime, so the
+/// exponentiation goes through the ladder. Fails if `value` is not a
+/// quadratic residue modulo `p`, in which case it cannot be a ciphertext.
+fn square_root_mod(value: &UBig, p: &UBig) -> Result<UBig> {
+    let exponent = (p + 1u8) >> 2;
+    let root = ct_powmod(value, &exponent, p);
+
+    match (&root * &root) % p == value % p {
+        true => Ok(root),
+        false => Err(Error::OutOfRange {
+            algorithm: Algorithm::Rabin,
+            reason: "ciphertext is not a quadratic residue",
+        }),
+    }
+}
+
+/// Tells whether `value` is a quadratic non-residue modulo the odd prime `p`.
+///
+/// By Euler's criterion, `value ^ ((p - 1) / 2)` is `p - 1` exactly for the
+/// non-residues. The exponent is derived from the secret prime, so the
+/// exponentiation goes through the ladder.
+fn is_non_residue(value: &UBig, p: &UBig) -> bool {
+    let exponent = (p - 1u8) >> 1;
+    ct_powmod(value, &exponent, p) == p - 1u8
+}
+
+/// Looks for `MAGIC` at the top of the number encoded in the little-endian `bytes`,
+/// that is, followed only by zero padding, and returns where it starts.
+///
+/// The bytes below that position are the message. Every possible position is checked,
+/// so the time taken does not depend on the contents. As `MAGIC` ends in a non-zero
+/// byte, at most one position can match.
+fn find_magic(bytes: &[u8]) -> (bool, usize) {
+    let mut found = false;
+    let mut position = 0;
+    let mut tail_is_zero = true;
+
+    for end in (MAGIC.len()..=bytes.len()).rev() {
+        let hit = tail_is_zero & ct_eq(&bytes[end - MAGIC.len()..end], MAGIC);
+        position |= (hit as usize).wrapping_neg() & (end - MAGIC.len());
+        found |= hit;
+        tail_is_zero &= bytes[end - 1] == 0;
+    }
+
+    (found, position)
 }
@@ -1,8 +1,9 @@
 use ibig::{ubig, UBig};
-use ibig_ext::powmod::PowMod;
 
-use super::MAGIC;
-use crate::result::Error;
+use super::{full_domain_hash, RabinCiphertext, RabinSignature, MAGIC};
+use crate::keypair::Verifier;
+use crate::result::{Algorithm, Error};
+use crate::utils::{ct_eq, has_small_factor, to_fixed_le_bytes, PowMod, MIN_MODULUS_BITS};
 use crate::{keypair::PublicKey, result::Result};
 
 /// Public key for the Rabin cryptosystem.
@@ -13,18 +14,124 @@ pub struct RabinPublic {
     pub divisor: UBig,
 }
 
-impl PublicKey for RabinPublic {
-    #[inline]
-    fn encrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
-        let mut message = message.to_vec();
+impl RabinPublic {
+    /// Checks that the key is plausible before it is used.
+    ///
+    /// The modulus must be at least `MIN_MODULUS_BITS` wide, free of small factors and
+    /// congruent to 1 modulo 4, as a product of two primes congruent to 3 modulo 4 is.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |reason| {
+            Err(Error::InvalidKey {
+                algorithm: Algorithm::Rabin,
+                reason,
+            })
+        };
+
+        if self.divisor.bit_len() < MIN_MODULUS_BITS {
+            return invalid("modulus is too small");
+        }
+
+        if &self.divisor % 2u8 == 0u8 {
+            return invalid("modulus is even");
+        }
+
+        if has_small_factor(&self.divisor) {
+            return invalid("modulus has a small factor");
+        }
+
+        if &self.divisor % 4u8 != 1u8 {
+            return invalid("modulus is not congruent to 1 modulo 4");
+        }
+
+        Ok(())
+    }
+
+    /// Encrypts a single block into a typed ciphertext.
+    ///
+    /// Blocks longer than [`PublicKey::max_message_size`] are rejected even if they would
+    /// fit below the modulus, as the padded block must stay clear of its top bit.
+    pub fn encrypt_block(&self, bytes: &[u8]) -> Result<RabinCiphertext> {
+        if bytes.len() > self.max_message_size() {
+            return Err(Error::MessageTooLarge {
+                algorithm: Algorithm::Rabin,
+                max: self.max_message_size(),
+                actual: bytes.len(),
+            });
+        }
+
+        let mut message = bytes.to_vec();
         message.extend(MAGIC);
 
         let message = UBig::from_le_bytes(&message);
-        if message >= self.divisor {
-            return Err(Error::SmallKey);
+        let message = message.powmod(ubig!(2), &self.divisor);
+        Ok(RabinCiphertext(message))
+    }
+}
+
+impl RabinPublic {
+    /// Verifies a typed Rabin–Williams signature over an already computed hash of the message.
+    ///
+    /// Checking `e * f * s ^ 2 ≡ h (mod n)` takes a single modular squaring.
+    pub fn verify_signature(&self, hash: &[u8], signature: &RabinSignature) -> Result<bool> {
+        let RabinSignature { e, f, s } = signature;
+        let n = &self.divisor;
+
+        if !matches!(*e, 1 | -1) || !matches!(*f, 1 | 2) {
+            return Err(Error::OutOfRange {
+                algorithm: Algorithm::Rabin,
+                reason: "signature tweaks are not in {1, -1} and {1, 2}",
+            });
         }
 
-        let message = message.powmod(ubig!(2), &self.divisor);
-        Ok(message.to_le_bytes())
+        if s >= n {
+            return Err(Error::OutOfRange {
+                algorithm: Algorithm::Rabin,
+                reason: "signature is not smaller than the modulus",
+            });
+        }
+
+        let mut out = (s * s) % n;
+        if *f == 2 {
+            out = (out << 1) % n;
+        }
+        if *e == -1 && out != ubig!(0) {
+            out = n - out;
+        }
+
+        let length = n.bit_len().div_ceil(8);
+        Ok(ct_eq(
+            &to_fixed_le_bytes(&out, length),
+            &to_fixed_le_bytes(&full_domain_hash(hash, n), length),
+        ))
+    }
+}
+
+impl Verifier for RabinPublic {
+    #[inline]
+    fn verify_hash(&self, hash: &[u8], signed_data: &[u8]) -> Result<bool> {
+        self.verify_signature(hash, &RabinSignature::from_bytes(signed_data)?)
+    }
+}
+
+impl PublicKey for RabinPublic {
+    #[inline]
+    fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        self.encrypt_block(bytes).map(|c| c.to_bytes())
+    }
+
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.divisor.bit_len()
+    }
+
+    /// The `MAGIC` suffix occupies part of every block.
+    #[inline]
+    fn max_message_size(&self) -> usize {
+        (self.bit_length().saturating_sub(1) / 8).saturating_sub(MAGIC.len())
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Rabin
     }
 }
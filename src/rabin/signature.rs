@@ -0,0 +1,73 @@
+use ibig::UBig;
+
+use crate::result::{Error, Result};
+use crate::utils::{tag_bytes, to_fixed_le_bytes, untag_bytes, TypeTag};
+
+/// A Rabin–Williams signature over the hash of a message.
+///
+/// The tweaks `e` and `f` make `e * f * s ^ 2 mod n` equal to the full-domain
+/// hash of the message, even when the hash itself is not a square.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RabinSignature {
+    /// The tweak `e`, either `1` or `-1`.
+    pub e: i8,
+
+    /// The tweak `f`, either `1` or `2`.
+    pub f: u8,
+
+    /// The square root `s`.
+    pub s: UBig,
+}
+
+impl RabinSignature {
+    /// Returns the byte that packs both tweaks, with `e = -1` in the lowest bit
+    /// and `f = 2` in the next one.
+    #[inline]
+    fn tweak_byte(&self) -> u8 {
+        (self.e == -1) as u8 | (((self.f == 2) as u8) << 1)
+    }
+
+    /// Encodes the signature as the type tag and the tweak byte followed by `s`
+    /// in little-endian bytes.
+    #[inline]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_fixed_bytes(self.s.bit_len().div_ceil(8))
+    }
+
+    /// Encodes the signature like [`RabinSignature::to_bytes`], with `s` padded to
+    /// exactly `length` bytes so that every signature of a key has the same size.
+    pub(crate) fn to_fixed_bytes(&self, length: usize) -> Vec<u8> {
+        let mut bytes = vec![self.tweak_byte()];
+        bytes.extend(to_fixed_le_bytes(&self.s, length));
+        tag_bytes(TypeTag::RabinSignature, &bytes)
+    }
+
+    /// Decodes a signature produced by [`RabinSignature::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let bytes = untag_bytes(TypeTag::RabinSignature, bytes)?;
+        let (&tweak, s) = bytes.split_first().ok_or(Error::MalformedInput {
+            reason: "signature is empty",
+            chunk: None,
+        })?;
+
+        if tweak > 0b11 {
+            return Err(Error::MalformedInput {
+                reason: "unknown signature tweak",
+                chunk: None,
+            });
+        }
+
+        Ok(Self {
+            e: match tweak & 1 == 1 {
+                true => -1,
+                false => 1,
+            },
+            f: match tweak & 2 == 2 {
+                true => 2,
+                false => 1,
+            },
+            s: UBig::from_le_bytes(s),
+        })
+    }
+}
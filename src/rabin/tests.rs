@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+
+use rand::{rngs::StdRng, SeedableRng};
+use sha2::{Digest, Sha256};
+
+use super::{RabinCiphertext, RabinKeyPair, RabinSignature};
+use crate::keypair::{PrivateKey, PublicKey, Signer, Verifier};
+use crate::result::Error;
+
+const MESSAGE: [u8; 445] = *b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.";
+
+#[test]
+fn test_encrypt_decrypt() {
+    let key = RabinKeyPair::new(128, 5).unwrap();
+    assert!(key.public.validate().is_ok());
+
+    let encrypted = key.encrypt_chunked(&MESSAGE).unwrap();
+    let decrypted = key.decrypt_chunked(&encrypted).unwrap();
+    assert_eq!(MESSAGE, decrypted.as_slice());
+
+    let ciphertext = key.public.encrypt_block(b"Lorem").unwrap();
+    assert_eq!(key.private.decrypt_block(&ciphertext).unwrap(), b"Lorem");
+}
+
+#[test]
+fn test_max_message_size() {
+    let key = RabinKeyPair::new_with_rng(128, 5, &mut StdRng::seed_from_u64(3)).unwrap();
+
+    let block = vec![0xff; key.max_message_size()];
+    let encrypted = key.encrypt(&block).unwrap();
+    assert_eq!(key.decrypt(&encrypted).unwrap(), block);
+
+    let block = vec![0xff; key.max_message_size() + 1];
+    assert!(matches!(
+        key.encrypt(&block),
+        Err(Error::MessageTooLarge { .. })
+    ));
+}
+
+#[test]
+fn test_encrypt_decrypt_trailing_zeros() {
+    let key = RabinKeyPair::new(128, 5).unwrap();
+    let message = [0u8; 100];
+
+    let encrypted = key.encrypt_chunked(&message).unwrap();
+    let decrypted = key.decrypt_chunked(&encrypted).unwrap();
+    assert_eq!(message, decrypted.as_slice());
+
+    let ciphertext = key.public.encrypt_block(b"Lorem\0\0").unwrap();
+    assert_eq!(
+        key.private.decrypt_block(&ciphertext).unwrap(),
+        b"Lorem\0\0"
+    );
+}
+
+#[test]
+fn test_rejects_invalid_ciphertext() {
+    let key = RabinKeyPair::new(128, 5).unwrap();
+
+    // Any square without `MAGIC` at the top of one of its roots is rejected.
+    let unpadded = RabinCiphertext(ibig::ubig!(49));
+    assert!(matches!(
+        key.private.decrypt_block(&unpadded),
+        Err(Error::Padding { .. })
+    ));
+
+    let n = &key.public.divisor;
+    assert!(matches!(
+        key.private.decrypt_block(&RabinCiphertext(n.clone())),
+        Err(Error::OutOfRange { .. })
+    ));
+}
+
+#[test]
+fn test_sign_verify() {
+    fn hashf(b: &[u8]) -> Vec<u8> {
+        b.iter()
+            .map(|x| *x as u64)
+            .sum::<u64>()
+            .to_le_bytes()
+            .to_vec()
+    }
+
+    let key = RabinKeyPair::new(128, 5).unwrap();
+
+    let signed = key.sign_chunked(&MESSAGE, hashf, 16).unwrap();
+    let verified = key.verify_chunked(&MESSAGE, &signed, hashf, 16).unwrap();
+    assert!(verified);
+
+    let signed = key.sign_digest(Sha256::new_with_prefix(MESSAGE)).unwrap();
+    assert!(key
+        .verify_digest(Sha256::new_with_prefix(MESSAGE), &signed)
+        .unwrap());
+    assert!(!key
+        .verify_digest(Sha256::new_with_prefix(b"Lorem"), &signed)
+        .unwrap());
+}
+
+#[test]
+fn test_signature_tweaks() {
+    let key = RabinKeyPair::new_with_rng(128, 5, &mut StdRng::seed_from_u64(5)).unwrap();
+
+    // Every combination of the tweaks shows up across enough hashes, and each
+    // signature only verifies with the tweaks it was made with.
+    let mut tweaks = HashSet::new();
+    for i in 0u8..64 {
+        let hash = Sha256::digest([i]);
+        let signature = key.private.create_signature(&hash).unwrap();
+        assert!(key.public.verify_signature(&hash, &signature).unwrap());
+
+        let flipped = RabinSignature {
+            e: -signature.e,
+            ..signature.clone()
+        };
+        assert!(!key.public.verify_signature(&hash, &flipped).unwrap());
+
+        let parsed = RabinSignature::from_bytes(&signature.to_bytes()).unwrap();
+        assert_eq!(parsed, signature);
+        let signed = key.sign_hash(&hash).unwrap();
+        assert_eq!(signed.len(), 2 + key.bit_length().div_ceil(8));
+        assert_eq!(RabinSignature::from_bytes(&signed).unwrap(), signature);
+        tweaks.insert((signature.e, signature.f));
+    }
+    assert_eq!(tweaks.len(), 4);
+
+    let invalid = RabinSignature {
+        e: 2,
+        f: 1,
+        s: 1u8.into(),
+    };
+    assert!(matches!(
+        key.public.verify_signature(b"Lorem", &invalid),
+        Err(Error::OutOfRange { .. })
+    ));
+
+    let ciphertext = key.public.encrypt_block(b"Lorem").unwrap();
+    assert!(matches!(
+        RabinSignature::from_bytes(&ciphertext.to_bytes()),
+        Err(Error::MalformedInput { .. })
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_typed_serialization() {
+    let key = RabinKeyPair::new(128, 5).unwrap();
+
+    let ciphertext = key.public.encrypt_block(b"Lorem").unwrap();
+    let json = serde_json::to_string(&ciphertext).unwrap();
+    assert_eq!(
+        serde_json::from_str::<RabinCiphertext>(&json).unwrap(),
+        ciphertext
+    );
+
+    let signature = key
+        .private
+        .create_signature(&Sha256::digest(MESSAGE))
+        .unwrap();
+    let json = serde_json::to_string(&signature).unwrap();
+    assert_eq!(
+        serde_json::from_str::<RabinSignature>(&json).unwrap(),
+        signature
+    );
+}
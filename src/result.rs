@@ -1,12 +1,205 @@
-#[derive(Debug)]
+use std::fmt;
+
+/// Identifies the cryptosystem an error originated from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Rsa,
+    Rabin,
+    ElGamal,
+    Paillier,
+    DamgardJurik,
+    GoldwasserMicali,
+    OkamotoUchiyama,
+    CramerShoup,
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Algorithm::Rsa => "RSA",
+            Algorithm::Rabin => "Rabin",
+            Algorithm::ElGamal => "ElGamal",
+            Algorithm::Paillier => "Paillier",
+            Algorithm::DamgardJurik => "Damgård–Jurik",
+            Algorithm::GoldwasserMicali => "Goldwasser–Micali",
+            Algorithm::OkamotoUchiyama => "Okamoto–Uchiyama",
+            Algorithm::CramerShoup => "Cramer–Shoup",
+        };
+
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Error {
-    /// Indicates that the provided key is smaller than the provided message.
-    SmallKey,
+    /// Indicates that the message does not fit into a single block of the key.
+    MessageTooLarge {
+        algorithm: Algorithm,
+        /// Maximum number of bytes the key accepts.
+        max: usize,
+        /// Number of bytes that were provided.
+        actual: usize,
+    },
+
+    /// Indicates that the input is not well-formed.
+    MalformedInput {
+        reason: &'static str,
+        /// Index of the chunk the error occurred in, if any.
+        chunk: Option<usize>,
+    },
+
+    /// Indicates that the input ends before the number of bytes it announces.
+    Truncated {
+        /// Number of bytes announced by the input.
+        expected: usize,
+        /// Number of bytes actually available.
+        actual: usize,
+        /// Index of the chunk the error occurred in, if any.
+        chunk: Option<usize>,
+    },
+
+    /// Indicates that a ciphertext or signature lies outside the range or group the key works in.
+    OutOfRange {
+        algorithm: Algorithm,
+        reason: &'static str,
+    },
+
+    /// Indicates that the key cannot be used for the requested operation.
+    InvalidKey {
+        algorithm: Algorithm,
+        reason: &'static str,
+    },
+
+    /// Indicates that the decrypted data lacks the expected padding or redundancy,
+    /// so the original message could not be found.
+    Padding {
+        algorithm: Algorithm,
+        /// Index of the chunk the error occurred in, if any.
+        chunk: Option<usize>,
+    },
+
+    /// Indicates that a signature or an authentication tag did not verify,
+    /// or that authenticated chunks were modified, reordered or truncated.
+    Verification {
+        algorithm: Algorithm,
+        /// Index of the chunk the error occurred in, if any.
+        chunk: Option<usize>,
+    },
+
+    /// Indicates that the input was written in a format version that is not supported.
+    UnsupportedFormat { expected: u8, actual: u8 },
+
+    /// Indicates that a deterministic random bit generator must be reseeded
+    /// before it produces more output.
+    ReseedRequired,
+
+    /// Indicates that key generation failed.
+    KeyGeneration {
+        algorithm: Algorithm,
+        reason: &'static str,
+    },
+
+    /// Indicates that the cryptosystem does not support the requested operation.
+    Unsupported {
+        algorithm: Algorithm,
+        operation: &'static str,
+    },
+}
+
+impl Error {
+    /// Attaches the index of the chunk the error occurred in, if the variant records one.
+    pub fn at_chunk(mut self, index: usize) -> Self {
+        match &mut self {
+            Error::MalformedInput { chunk, .. }
+            | Error::Truncated { chunk, .. }
+            | Error::Padding { chunk, .. }
+            | Error::Verification { chunk, .. } => *chunk = Some(index),
+            _ => {}
+        }
+
+        self
+    }
+}
+
+/// Formats ` in chunk N` if a chunk index is known.
+struct ChunkSuffix(Option<usize>);
+
+impl fmt::Display for ChunkSuffix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(index) => write!(f, " in chunk {index}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MessageTooLarge {
+                algorithm,
+                max,
+                actual,
+            } => write!(
+                f,
+                "{algorithm} message of {actual} bytes exceeds the key limit of {max} bytes"
+            ),
+            Error::MalformedInput { reason, chunk } => {
+                write!(f, "malformed input{}: {reason}", ChunkSuffix(*chunk))
+            }
+            Error::Truncated {
+                expected,
+                actual,
+                chunk,
+            } => write!(
+                f,
+                "truncated input{}: expected {expected} bytes, found {actual}",
+                ChunkSuffix(*chunk)
+            ),
+            Error::OutOfRange { algorithm, reason } => {
+                write!(f, "{algorithm} input out of range: {reason}")
+            }
+            Error::InvalidKey { algorithm, reason } => {
+                write!(f, "invalid {algorithm} key: {reason}")
+            }
+            Error::Padding { algorithm, chunk } => {
+                write!(f, "invalid {algorithm} padding{}", ChunkSuffix(*chunk))
+            }
+            Error::Verification { algorithm, chunk } => {
+                write!(f, "{algorithm} verification failed{}", ChunkSuffix(*chunk))
+            }
+            Error::UnsupportedFormat { expected, actual } => write!(
+                f,
+                "unsupported format version {actual}, expected {expected}"
+            ),
+            Error::ReseedRequired => f.write_str("random bit generator must be reseeded"),
+            Error::KeyGeneration { algorithm, reason } => {
+                write!(f, "{algorithm} key generation failed: {reason}")
+            }
+            Error::Unsupported {
+                algorithm,
+                operation,
+            } => write!(f, "{algorithm} does not support {operation}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
 
-    /// Indicates that the original message could not be found.
-    MessageNotFound,
+impl From<Error> for std::io::Error {
+    fn from(error: Error) -> Self {
+        let kind = match error {
+            Error::MessageTooLarge { .. } | Error::InvalidKey { .. } => {
+                std::io::ErrorKind::InvalidInput
+            }
+            Error::KeyGeneration { .. } | Error::ReseedRequired => std::io::ErrorKind::Other,
+            Error::Unsupported { .. } => std::io::ErrorKind::Unsupported,
+            Error::Truncated { .. } => std::io::ErrorKind::UnexpectedEof,
+            _ => std::io::ErrorKind::InvalidData,
+        };
 
-    MathError,
+        std::io::Error::new(kind, error)
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -1,10 +1,14 @@
+mod ciphertext;
 mod pair;
 mod private;
 mod public;
+mod signature;
 
+pub use ciphertext::RsaCiphertext;
 pub use pair::RsaKeyPair;
 pub use private::RsaPrivate;
 pub use public::RsaPublic;
+pub use signature::RsaSignature;
 
 #[cfg(test)]
 mod tests;
@@ -1,8 +1,10 @@
-use ibig::{ops::RemEuclid, ubig, IBig, UBig};
-use ibig_ext::prime_gen::gen_sized_prime;
+use ibig::{ubig, UBig};
+use rand::{CryptoRng, RngCore};
 
 use super::{RsaPrivate, RsaPublic};
-use crate::{result::Result, PrivateKey, PublicKey};
+use crate::result::{Algorithm, Error, Result};
+use crate::utils::{check_prime_bits, gen_prime, imod};
+use crate::{PrivateKey, PublicKey};
 use crate::{Signer, Verifier};
 
 /// An RSA key pair for encryption and decryption.
@@ -22,30 +24,58 @@ impl RsaKeyPair {
     /// * `bit_length`: The desired bit length for the keys in the pair.
     /// * `persistence`: The number of iterations for checking numbers for primality.
     ///
-    /// Returns the newly generated `RsaKeyPair` instance.
-    pub fn new(bit_length: usize, persistence: usize) -> Self {
-        let p = gen_sized_prime(bit_length, persistence);
-        let q = gen_sized_prime(bit_length, persistence);
+    /// Returns the newly generated `RsaKeyPair` instance, or
+    /// [`Error::KeyGeneration`](crate::result::Error::KeyGeneration) if `bit_length` is too small.
+    #[inline]
+    pub fn new(bit_length: usize, persistence: usize) -> Result<Self> {
+        Self::new_with_rng(bit_length, persistence, &mut rand::thread_rng())
+    }
+
+    /// Generates a new RSA key pair, drawing all randomness from `rng`.
+    ///
+    /// The same seeded generator always yields the same key pair.
+    pub fn new_with_rng<R: RngCore + CryptoRng>(
+        bit_length: usize,
+        persistence: usize,
+        rng: &mut R,
+    ) -> Result<Self> {
+        check_prime_bits(Algorithm::Rsa, bit_length)?;
+
+        let e: UBig = ubig!(2).pow(16) + 1;
+
+        // The exponent is prime, so it is invertible unless it divides `p - 1`.
+        let coprime = |p: &UBig| (p - 1u8) % &e != ubig!(0);
+        let p = gen_prime(bit_length, persistence, rng, coprime);
+        let q = loop {
+            let q = gen_prime(bit_length, persistence, rng, coprime);
+            if q != p {
+                break q;
+            }
+        };
 
         let n = &p * &q;
         let totient = (&p - 1) * (&q - 1);
 
-        let e: UBig = ubig!(2).pow(16) + 1;
-        let (_, d, _) = e.extended_gcd(&totient);
-        let d = d.rem_euclid(IBig::from(totient));
-        let d: UBig = d.try_into().expect("Cannot convert d to UBig");
+        let (gcd, d, _) = e.extended_gcd(&totient);
+        if gcd != ubig!(1) {
+            return Err(Error::KeyGeneration {
+                algorithm: Algorithm::Rsa,
+                reason: "public exponent is not invertible",
+            });
+        }
+        let d = imod(&d, &totient);
 
-        Self {
+        Ok(Self {
             public: RsaPublic {
                 exponent: e,
                 divisor: n,
             },
             private: RsaPrivate {
-                exponent: d,
-                prime_1: p,
-                prime_2: q,
+                exponent: d.into(),
+                prime_1: p.into(),
+                prime_2: q.into(),
             },
-        }
+        })
     }
 }
 
@@ -54,6 +84,11 @@ impl PrivateKey for RsaKeyPair {
     fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
         self.private.decrypt(message)
     }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Rsa
+    }
 }
 
 impl PublicKey for RsaKeyPair {
@@ -61,23 +96,33 @@ impl PublicKey for RsaKeyPair {
     fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
         self.public.encrypt(bytes)
     }
+
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.public.bit_length()
+    }
+
+    #[inline]
+    fn max_message_size(&self) -> usize {
+        self.public.max_message_size()
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Rsa
+    }
 }
 
 impl Verifier for RsaKeyPair {
     #[inline]
-    fn verify(
-        &self,
-        expected: &[u8],
-        signed_data: &[u8],
-        hashf: fn(&[u8]) -> Vec<u8>,
-    ) -> Result<bool> {
-        self.public.verify(expected, signed_data, hashf)
+    fn verify_hash(&self, hash: &[u8], signed_data: &[u8]) -> Result<bool> {
+        self.public.verify_hash(hash, signed_data)
     }
 }
 
 impl Signer for RsaKeyPair {
     #[inline]
-    fn sign(&self, data: &[u8], hashf: fn(&[u8]) -> Vec<u8>) -> Result<Vec<u8>> {
-        self.private.sign(data, hashf)
+    fn sign_hash(&self, hash: &[u8]) -> Result<Vec<u8>> {
+        self.private.sign_hash(hash)
     }
 }
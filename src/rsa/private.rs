@@ -1,7 +1,10 @@
-use ibig::UBig;
-use ibig_ext::powmod::PowMod;
+use ibig::{ubig, UBig};
 
+use super::{RsaCiphertext, RsaSignature};
 use crate::keypair::Signer;
+use crate::result::{Algorithm, Error};
+use crate::secret::SecretUBig;
+use crate::utils::{bits_to_int, ct_powmod};
 use crate::{keypair::PrivateKey, result::Result};
 
 /// Private key for the RSA algorithm.
@@ -9,31 +12,80 @@ use crate::{keypair::PrivateKey, result::Result};
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RsaPrivate {
     /// Private exponent used for decryption.
-    pub exponent: UBig,
+    pub exponent: SecretUBig,
 
     /// First prime factor of the public modulus.
-    pub prime_1: UBig,
+    pub prime_1: SecretUBig,
 
     /// Second prime factor of the public modulus.
-    pub prime_2: UBig,
+    pub prime_2: SecretUBig,
+}
+
+impl RsaPrivate {
+    /// Returns the public modulus, the product of both primes.
+    ///
+    /// Fails with [`Error::InvalidKey`] if the modulus is below 2 or narrower than the
+    /// exponent, which only a key that was never generated or validated can have.
+    fn divisor(&self) -> Result<UBig> {
+        let invalid = |reason| {
+            Err(Error::InvalidKey {
+                algorithm: Algorithm::Rsa,
+                reason,
+            })
+        };
+
+        let divisor = self.prime_1.expose() * self.prime_2.expose();
+        if divisor < ubig!(2) {
+            return invalid("modulus is too small");
+        }
+
+        if self.exponent.expose().bit_len() > divisor.bit_len() {
+            return invalid("exponent is wider than the modulus");
+        }
+
+        Ok(divisor)
+    }
+
+    /// Decrypts a single typed ciphertext block.
+    pub fn decrypt_block(&self, ciphertext: &RsaCiphertext) -> Result<Vec<u8>> {
+        let div = self.divisor()?;
+        if ciphertext.0 >= div {
+            return Err(Error::OutOfRange {
+                algorithm: Algorithm::Rsa,
+                reason: "ciphertext is not smaller than the modulus",
+            });
+        }
+
+        let out = ct_powmod(&ciphertext.0, self.exponent.expose(), &div);
+        Ok(out.to_le_bytes())
+    }
+
+    /// Signs an already computed hash of the message.
+    pub fn create_signature(&self, hash: &[u8]) -> Result<RsaSignature> {
+        let divisor = self.divisor()?;
+        let data_hash = bits_to_int(hash, divisor.bit_len() - 1);
+
+        let signature = ct_powmod(&data_hash, self.exponent.expose(), &divisor);
+        Ok(RsaSignature(signature))
+    }
 }
 
 impl PrivateKey for RsaPrivate {
+    #[inline]
     fn decrypt(&self, message: &[u8]) -> Result<Vec<u8>> {
-        let div = &self.prime_1 * &self.prime_2;
-        let message = UBig::from_le_bytes(message);
-        let out = message.powmod(self.exponent.clone(), &div);
+        self.decrypt_block(&RsaCiphertext::from_bytes(message)?)
+    }
 
-        Ok(out.to_le_bytes())
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Rsa
     }
 }
 
 impl Signer for RsaPrivate {
-    fn sign(&self, data: &[u8], hashf: fn(&[u8]) -> Vec<u8>) -> Result<Vec<u8>> {
-        let divisor = &self.prime_1 * &self.prime_2;
-        let data_hash = UBig::from_le_bytes(&hashf(data));
-
-        let message = data_hash.powmod(self.exponent.clone(), &divisor);
-        Ok(message.to_le_bytes())
+    #[inline]
+    fn sign_hash(&self, hash: &[u8]) -> Result<Vec<u8>> {
+        let signature = self.create_signature(hash)?;
+        Ok(signature.to_fixed_bytes(self.divisor()?.bit_len().div_ceil(8)))
     }
 }
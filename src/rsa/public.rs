@@ -1,8 +1,13 @@
-use ibig::UBig;
-use ibig_ext::powmod::PowMod;
+use ibig::{ubig, UBig};
+use rand::{CryptoRng, RngCore};
 
+use super::{RsaCiphertext, RsaSignature};
+use crate::homomorphic::Homomorphic;
 use crate::keypair::Verifier;
-use crate::result::Error;
+use crate::result::{Algorithm, Error};
+use crate::utils::{
+    bits_to_int, ct_eq, has_small_factor, to_fixed_le_bytes, PowMod, MIN_MODULUS_BITS,
+};
 use crate::{keypair::PublicKey, result::Result};
 
 /// Public key for the RSA algorithm.
@@ -16,34 +21,153 @@ pub struct RsaPublic {
     pub divisor: UBig,
 }
 
-impl PublicKey for RsaPublic {
-    #[inline]
-    fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+impl RsaPublic {
+    /// Checks that the key is plausible before it is used.
+    ///
+    /// The modulus must be odd, at least `MIN_MODULUS_BITS` wide and free of small factors,
+    /// and the exponent must be odd and lie between 3 and the modulus.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |reason| {
+            Err(Error::InvalidKey {
+                algorithm: Algorithm::Rsa,
+                reason,
+            })
+        };
+
+        if self.divisor.bit_len() < MIN_MODULUS_BITS {
+            return invalid("modulus is too small");
+        }
+
+        if &self.divisor % 2u8 == 0u8 {
+            return invalid("modulus is even");
+        }
+
+        if has_small_factor(&self.divisor) {
+            return invalid("modulus has a small factor");
+        }
+
+        if self.exponent < ubig!(3) || self.exponent >= self.divisor {
+            return invalid("exponent is out of range");
+        }
+
+        if &self.exponent % 2u8 == 0u8 {
+            return invalid("exponent is even");
+        }
+
+        Ok(())
+    }
+
+    /// Encrypts a single block into a typed ciphertext.
+    pub fn encrypt_block(&self, bytes: &[u8]) -> Result<RsaCiphertext> {
         let message = UBig::from_le_bytes(bytes);
         if message >= self.divisor {
-            return Err(Error::SmallKey);
+            return Err(Error::MessageTooLarge {
+                algorithm: Algorithm::Rsa,
+                max: self.max_message_size(),
+                actual: bytes.len(),
+            });
         }
 
         let message = message.powmod(self.exponent.clone(), &self.divisor);
-        Ok(message.to_le_bytes())
+        Ok(RsaCiphertext(message))
+    }
+
+    /// Verifies a typed signature over an already computed hash of the message.
+    ///
+    /// Fails with [`Error::InvalidKey`] if the modulus is below 2.
+    pub fn verify_signature(&self, hash: &[u8], signature: &RsaSignature) -> Result<bool> {
+        if self.divisor < ubig!(2) {
+            return Err(Error::InvalidKey {
+                algorithm: Algorithm::Rsa,
+                reason: "modulus is too small",
+            });
+        }
+
+        let expected_hash = bits_to_int(hash, self.divisor.bit_len() - 1);
+
+        if signature.0 >= self.divisor {
+            return Err(Error::OutOfRange {
+                algorithm: Algorithm::Rsa,
+                reason: "signature is not smaller than the modulus",
+            });
+        }
+
+        let length = self.divisor.bit_len().div_ceil(8);
+        let out = signature.0.powmod(self.exponent.clone(), &self.divisor);
+        Ok(ct_eq(
+            &to_fixed_le_bytes(&out, length),
+            &to_fixed_le_bytes(&expected_hash, length),
+        ))
     }
 }
 
-impl Verifier for RsaPublic {
-    fn verify(
+impl RsaPublic {
+    /// Ensures that the ciphertext lies below the modulus.
+    fn check_ciphertext(&self, ciphertext: &RsaCiphertext) -> Result<()> {
+        match ciphertext.0 < self.divisor {
+            true => Ok(()),
+            false => Err(Error::OutOfRange {
+                algorithm: Algorithm::Rsa,
+                reason: "ciphertext is not smaller than the modulus",
+            }),
+        }
+    }
+}
+
+/// Textbook RSA is multiplicatively homomorphic: `E(a) * E(b) = E(a * b mod n)`.
+impl Homomorphic for RsaPublic {
+    type Ciphertext = RsaCiphertext;
+
+    fn mul(&self, a: &RsaCiphertext, b: &RsaCiphertext) -> Result<RsaCiphertext> {
+        self.check_ciphertext(a)?;
+        self.check_ciphertext(b)?;
+
+        Ok(RsaCiphertext((&a.0 * &b.0) % &self.divisor))
+    }
+
+    fn pow(&self, ciphertext: &RsaCiphertext, exponent: &UBig) -> Result<RsaCiphertext> {
+        self.check_ciphertext(ciphertext)?;
+
+        Ok(RsaCiphertext(
+            ciphertext.0.powmod(exponent.clone(), &self.divisor),
+        ))
+    }
+
+    /// Textbook RSA is deterministic, so there is no fresh encryption of the
+    /// same plaintext and this always fails with [`Error::Unsupported`].
+    #[inline]
+    fn rerandomize_with_rng<R: RngCore + CryptoRng>(
         &self,
-        expected: &[u8],
-        signed_data: &[u8],
-        hashf: fn(&[u8]) -> Vec<u8>,
-    ) -> Result<bool> {
-        let expected_hash = hashf(expected);
-
-        let mut out = UBig::from_le_bytes(signed_data)
-            .powmod(self.exponent.clone(), &self.divisor)
-            .to_le_bytes()
-            .to_vec();
-        out.resize(expected_hash.len(), 0);
-
-        Ok(out.eq(&expected_hash))
+        _ciphertext: &RsaCiphertext,
+        _rng: &mut R,
+    ) -> Result<RsaCiphertext> {
+        Err(Error::Unsupported {
+            algorithm: Algorithm::Rsa,
+            operation: "rerandomization",
+        })
+    }
+}
+
+impl PublicKey for RsaPublic {
+    #[inline]
+    fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        self.encrypt_block(bytes).map(|c| c.to_bytes())
+    }
+
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.divisor.bit_len()
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Rsa
+    }
+}
+
+impl Verifier for RsaPublic {
+    #[inline]
+    fn verify_hash(&self, hash: &[u8], signed_data: &[u8]) -> Result<bool> {
+        self.verify_signature(hash, &RsaSignature::from_bytes(signed_data)?)
     }
 }
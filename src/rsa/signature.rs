@@ -0,0 +1,30 @@
+use ibig::UBig;
+
+use crate::result::Result;
+use crate::utils::{tag_bytes, to_fixed_le_bytes, untag_bytes, TypeTag};
+
+/// An RSA signature over the hash of a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RsaSignature(pub UBig);
+
+impl RsaSignature {
+    /// Encodes the signature as its type tag followed by little-endian bytes.
+    #[inline]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        tag_bytes(TypeTag::RsaSignature, &self.0.to_le_bytes())
+    }
+
+    /// Encodes the signature like [`RsaSignature::to_bytes`], padded to exactly
+    /// `length` bytes so that every signature of a key has the same size.
+    pub(crate) fn to_fixed_bytes(&self, length: usize) -> Vec<u8> {
+        tag_bytes(TypeTag::RsaSignature, &to_fixed_le_bytes(&self.0, length))
+    }
+
+    /// Decodes a signature produced by [`RsaSignature::to_bytes`].
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let bytes = untag_bytes(TypeTag::RsaSignature, bytes)?;
+        Ok(Self(UBig::from_le_bytes(bytes)))
+    }
+}
@@ -1,14 +1,22 @@
-use super::RsaKeyPair;
+use ibig::UBig;
+use rand::{rngs::StdRng, SeedableRng};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+use super::{RsaCiphertext, RsaKeyPair, RsaSignature};
 use crate::keypair::{PrivateKey, PublicKey, Signer, Verifier};
+use crate::result::Error;
+use crate::utils::{marshal_bytes, unmarshal_bytes};
+use crate::Homomorphic;
 
 const MESSAGE: [u8; 445] = *b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.";
 
 #[test]
 fn test_encrypt_decrypt() {
-    let key = RsaKeyPair::new(128, 5);
+    let key = RsaKeyPair::new(128, 5).unwrap();
 
-    let encrypted = key.encrypt_chunked(&MESSAGE, 8).unwrap();
-    let decrypted = key.decrypt_chunked(&encrypted, 8).unwrap();
+    let encrypted = key.encrypt_chunked(&MESSAGE).unwrap();
+    let decrypted = key.decrypt_chunked(&encrypted).unwrap();
     assert_eq!(MESSAGE, decrypted.as_slice());
 }
 
@@ -22,10 +30,282 @@ fn test_sign_verify() {
             .to_vec()
     }
 
-    let key = RsaKeyPair::new(128, 5);
+    let key = RsaKeyPair::new(128, 5).unwrap();
 
     let signed = key.sign_chunked(&MESSAGE, hashf, 16).unwrap();
     let verified = key.verify_chunked(&MESSAGE, &signed, hashf, 16).unwrap();
 
     assert!(verified);
 }
+
+#[test]
+fn test_max_message_size() {
+    let key = RsaKeyPair::new(128, 5).unwrap();
+
+    let block = vec![0xff; key.max_message_size()];
+    assert!(key.encrypt(&block).is_ok());
+
+    let block = vec![0xff; key.max_message_size() + 1];
+    assert!(key.encrypt(&block).is_err());
+}
+
+#[test]
+fn test_encrypt_decrypt_trailing_zeros() {
+    let key = RsaKeyPair::new(128, 5).unwrap();
+    let message = [0u8; 100];
+
+    let encrypted = key.encrypt_chunked(&message).unwrap();
+    let decrypted = key.decrypt_chunked(&encrypted).unwrap();
+    assert_eq!(message, decrypted.as_slice());
+}
+
+#[test]
+fn test_verify_chunked_rejects_tampering() {
+    fn hashf(b: &[u8]) -> Vec<u8> {
+        b.iter()
+            .map(|x| *x as u64)
+            .sum::<u64>()
+            .to_le_bytes()
+            .to_vec()
+    }
+
+    let key = RsaKeyPair::new(128, 5).unwrap();
+    let signed = key.sign_chunked(&MESSAGE, hashf, 16).unwrap();
+    let mut signatures = unmarshal_bytes(&signed).unwrap();
+
+    let extended = [MESSAGE.as_slice(), b"!"].concat();
+    assert!(!key.verify_chunked(&extended, &signed, hashf, 16).unwrap());
+
+    signatures.swap(0, 1);
+    let reordered = marshal_bytes(&signatures);
+    assert!(!key.verify_chunked(&MESSAGE, &reordered, hashf, 16).unwrap());
+
+    signatures.swap(0, 1);
+    signatures.pop();
+    let truncated = marshal_bytes(&signatures);
+    assert!(!key.verify_chunked(&MESSAGE, &truncated, hashf, 16).unwrap());
+}
+
+#[test]
+fn test_sign_verify_digest() {
+    let key = RsaKeyPair::new(128, 5).unwrap();
+
+    let mut digest = Sha256::new();
+    for chunk in MESSAGE.chunks(64) {
+        digest.update(chunk);
+    }
+
+    let signed = key.sign_digest(digest).unwrap();
+    assert_eq!(signed.len(), 1 + key.bit_length().div_ceil(8));
+
+    assert!(key
+        .verify_digest(Sha256::new_with_prefix(MESSAGE), &signed)
+        .unwrap());
+    assert!(!key
+        .verify_digest(Sha256::new_with_prefix(b"Lorem"), &signed)
+        .unwrap());
+}
+
+#[test]
+fn test_encrypt_decrypt_authenticated() {
+    let key = RsaKeyPair::new(128, 5).unwrap();
+
+    let encrypted = key.encrypt_authenticated(&MESSAGE).unwrap();
+    let decrypted = key.decrypt_authenticated(&encrypted).unwrap();
+    assert_eq!(MESSAGE, decrypted.as_slice());
+
+    let encrypted = key.encrypt_authenticated(&[]).unwrap();
+    let decrypted = key.decrypt_authenticated(&encrypted).unwrap();
+    assert!(decrypted.is_empty());
+}
+
+#[test]
+fn test_decrypt_authenticated_rejects_tampering() {
+    let key = RsaKeyPair::new(128, 5).unwrap();
+    let message = MESSAGE.repeat(20);
+    let encrypted = key.encrypt_authenticated(&message).unwrap();
+    let content = unmarshal_bytes(&encrypted).unwrap();
+    assert!(content.len() > 3);
+
+    let mut reordered = content.clone();
+    reordered.swap(1, 2);
+    assert!(key
+        .decrypt_authenticated(&marshal_bytes(&reordered))
+        .is_err());
+
+    let mut duplicated = content.clone();
+    duplicated.insert(2, duplicated[1].clone());
+    assert!(key
+        .decrypt_authenticated(&marshal_bytes(&duplicated))
+        .is_err());
+
+    let mut truncated = content.clone();
+    truncated.pop();
+    assert!(key
+        .decrypt_authenticated(&marshal_bytes(&truncated))
+        .is_err());
+
+    let mut forged = content;
+    let last = forged.len() - 1;
+    forged[last][0] ^= 1;
+    assert!(key.decrypt_authenticated(&marshal_bytes(&forged)).is_err());
+}
+
+#[test]
+fn test_decrypt_authenticated_rejects_rewrapped_records() {
+    let key = RsaKeyPair::new(128, 5).unwrap();
+    let encrypted = key.encrypt_authenticated(&MESSAGE.repeat(20)).unwrap();
+    let content = unmarshal_bytes(&encrypted).unwrap();
+
+    // Reorder the records and rewrite their sequence numbers and final flags
+    // so that the structure looks consistent again.
+    let mut records = content[1..].to_vec();
+    records.reverse();
+    let count = records.len();
+    for (sequence, record) in records.iter_mut().enumerate() {
+        record[..8].copy_from_slice(&(sequence as u64).to_le_bytes());
+        record[8] = (sequence + 1 == count) as u8;
+    }
+
+    // Only the public key is needed to wrap a fresh secret of the attacker's choosing.
+    let fresh = key.public.encrypt_chunked(&[7; 32]).unwrap();
+    for wrapped in [fresh, content[0].clone()] {
+        let mut rewrapped = vec![wrapped];
+        rewrapped.extend(records.iter().cloned());
+        assert!(matches!(
+            key.decrypt_authenticated(&marshal_bytes(&rewrapped)),
+            Err(Error::Verification { .. })
+        ));
+    }
+}
+
+#[test]
+fn test_private_key_redacted_and_wiped() {
+    let key = RsaKeyPair::new(128, 5).unwrap();
+
+    let debug = format!("{key:?}");
+    assert!(!debug.contains(&key.private.exponent.expose().to_string()));
+    assert!(!debug.contains(&key.private.prime_1.expose().to_string()));
+
+    let mut exponent = key.private.exponent.clone();
+    exponent.zeroize();
+    assert_eq!(*exponent.expose(), UBig::from(0u8));
+}
+
+#[test]
+fn test_validate_and_range_checks() {
+    let key = RsaKeyPair::new(128, 5).unwrap();
+    assert!(key.public.validate().is_ok());
+
+    let mut even = key.public.clone();
+    even.divisor += 1u8;
+    assert!(matches!(even.validate(), Err(Error::InvalidKey { .. })));
+
+    let too_large = RsaCiphertext(&key.public.divisor + 1u8).to_bytes();
+    assert!(matches!(
+        key.decrypt(&too_large),
+        Err(Error::OutOfRange { .. })
+    ));
+
+    // A key that was never validated must be rejected rather than panic.
+    let mut zero = key.clone();
+    zero.public.divisor = UBig::from(0u8);
+    zero.private.prime_1 = UBig::from(0u8).into();
+    let hash = Sha256::digest(MESSAGE);
+    let signature = RsaSignature(UBig::from(1u8));
+    assert!(matches!(
+        zero.private.create_signature(&hash),
+        Err(Error::InvalidKey { .. })
+    ));
+    assert!(matches!(
+        zero.private.decrypt_block(&RsaCiphertext(UBig::from(1u8))),
+        Err(Error::InvalidKey { .. })
+    ));
+    assert!(matches!(
+        zero.public.verify_signature(&hash, &signature),
+        Err(Error::InvalidKey { .. })
+    ));
+}
+
+#[test]
+fn test_typed_ciphertext_and_signature() {
+    let key = RsaKeyPair::new(128, 5).unwrap();
+
+    let ciphertext = key.public.encrypt_block(b"Lorem").unwrap();
+    let parsed = RsaCiphertext::from_bytes(&ciphertext.to_bytes()).unwrap();
+    assert_eq!(parsed, ciphertext);
+    assert_eq!(key.private.decrypt_block(&parsed).unwrap(), b"Lorem");
+
+    let hash = Sha256::digest(MESSAGE);
+    let signature = key.private.create_signature(&hash).unwrap();
+    let parsed = RsaSignature::from_bytes(&signature.to_bytes()).unwrap();
+    assert_eq!(parsed, signature);
+    assert!(key.public.verify_signature(&hash, &parsed).unwrap());
+
+    assert!(matches!(
+        RsaSignature::from_bytes(&ciphertext.to_bytes()),
+        Err(Error::MalformedInput { .. })
+    ));
+    assert!(matches!(
+        RsaCiphertext::from_bytes(&signature.to_bytes()),
+        Err(Error::MalformedInput { .. })
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_typed_serialization() {
+    let key = RsaKeyPair::new(128, 5).unwrap();
+
+    let ciphertext = key.public.encrypt_block(b"Lorem").unwrap();
+    let json = serde_json::to_string(&ciphertext).unwrap();
+    assert_eq!(
+        serde_json::from_str::<RsaCiphertext>(&json).unwrap(),
+        ciphertext
+    );
+
+    let signature = key
+        .private
+        .create_signature(&Sha256::digest(MESSAGE))
+        .unwrap();
+    let json = serde_json::to_string(&signature).unwrap();
+    assert_eq!(
+        serde_json::from_str::<RsaSignature>(&json).unwrap(),
+        signature
+    );
+}
+
+#[test]
+fn test_seeded_key_generation_is_deterministic() {
+    let a = RsaKeyPair::new_with_rng(128, 5, &mut StdRng::seed_from_u64(7)).unwrap();
+    let b = RsaKeyPair::new_with_rng(128, 5, &mut StdRng::seed_from_u64(7)).unwrap();
+    assert_eq!(a.public.divisor, b.public.divisor);
+    assert_eq!(a.private.exponent.expose(), b.private.exponent.expose());
+
+    let c = RsaKeyPair::new_with_rng(128, 5, &mut StdRng::seed_from_u64(8)).unwrap();
+    assert_ne!(a.public.divisor, c.public.divisor);
+}
+
+#[test]
+fn test_homomorphic_operations() {
+    let key = RsaKeyPair::new(128, 5).unwrap();
+    let n = &key.public.divisor;
+
+    let ca = key.public.encrypt_block(&[6]).unwrap();
+    let cb = key.public.encrypt_block(&[7]).unwrap();
+
+    let product = key.public.mul(&ca, &cb).unwrap();
+    assert_eq!(key.private.decrypt_block(&product).unwrap(), [42]);
+
+    let cubed = key.public.pow(&ca, &UBig::from(3u8)).unwrap();
+    assert_eq!(key.private.decrypt_block(&cubed).unwrap(), [216]);
+
+    assert!(matches!(
+        key.public.mul(&ca, &RsaCiphertext(n + 1u8)),
+        Err(Error::OutOfRange { .. })
+    ));
+    assert!(matches!(
+        key.public.rerandomize(&ca),
+        Err(Error::Unsupported { .. })
+    ));
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+
+use ibig::UBig;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// A secret component of a private key.
+///
+/// The value never shows up in `Debug` output and is overwritten with zeros when dropped.
+/// It can only be read through [`SecretUBig::expose`], which makes every use explicit.
+///
+/// Wiping is best effort: `ibig` does not expose its buffers, so copies made by
+/// arithmetic on the exposed value are not covered.
+#[derive(Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct SecretUBig(UBig);
+
+impl SecretUBig {
+    /// Wraps a secret value.
+    #[inline]
+    pub fn new(value: UBig) -> Self {
+        Self(value)
+    }
+
+    /// Returns a reference to the secret value.
+    #[inline]
+    pub fn expose(&self) -> &UBig {
+        &self.0
+    }
+}
+
+impl From<UBig> for SecretUBig {
+    #[inline]
+    fn from(value: UBig) -> Self {
+        Self::new(value)
+    }
+}
+
+impl fmt::Debug for SecretUBig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretUBig([REDACTED])")
+    }
+}
+
+impl Zeroize for SecretUBig {
+    /// Clears the bits from the least significant end, so the number keeps
+    /// its buffer until every word in it is zero and only then releases it.
+    fn zeroize(&mut self) {
+        for bit in 0..self.0.bit_len() {
+            self.0.clear_bit(bit);
+        }
+    }
+}
+
+impl Drop for SecretUBig {
+    #[inline]
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretUBig {}
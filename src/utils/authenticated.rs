@@ -0,0 +1,127 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::result::{Algorithm, Error, Result};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Size in bytes of the secret encapsulated at the start of an authenticated message.
+pub(crate) const SECRET_SIZE: usize = 32;
+
+/// Number of plaintext bytes sealed into a single record.
+pub(crate) const RECORD_SIZE: usize = 4096;
+
+/// Size of the sequence number and final flag in front of every record.
+const HEADER_SIZE: usize = 9;
+
+/// Size of the HMAC-SHA256 tag at the end of every record.
+const TAG_SIZE: usize = 32;
+
+const INNER_RECORD: u8 = 0x00;
+const FINAL_RECORD: u8 = 0x01;
+
+/// Symmetric keys derived from the encapsulated secret of one authenticated message.
+///
+/// Records are sealed with encrypt-then-MAC: the plaintext is XORed with an
+/// HMAC-SHA256 keystream and an HMAC-SHA256 tag covers the header and the
+/// ciphertext. As every message gets a fresh secret, the sequence number alone
+/// keeps the keystreams of its records apart.
+pub(crate) struct RecordKeys {
+    cipher: Zeroizing<Vec<u8>>,
+    mac: Zeroizing<Vec<u8>>,
+}
+
+impl RecordKeys {
+    /// Derives the encryption and MAC keys from the encapsulated secret.
+    pub(crate) fn derive(secret: &[u8]) -> Self {
+        Self {
+            cipher: Zeroizing::new(hmac(secret, &[b"encipher record cipher"])),
+            mac: Zeroizing::new(hmac(secret, &[b"encipher record mac"])),
+        }
+    }
+
+    /// Encrypts a chunk and returns it as a record carrying its sequence number,
+    /// the final flag and a tag that covers both.
+    pub(crate) fn seal(&self, sequence: usize, is_final: bool, chunk: &[u8]) -> Vec<u8> {
+        let mut record = encode_header(sequence, is_final);
+        record.extend(chunk);
+        apply_keystream(&self.cipher, sequence, &mut record[HEADER_SIZE..]);
+
+        let tag = hmac(&self.mac, &[&record]);
+        record.extend(tag);
+        record
+    }
+
+    /// Checks the header and tag of a record and returns the chunk it carries.
+    ///
+    /// Fails with [`Error::Verification`], reported under `algorithm`, if the record was
+    /// sealed under other keys, at another position, or with another final flag.
+    pub(crate) fn open(
+        &self,
+        algorithm: Algorithm,
+        record: &[u8],
+        sequence: usize,
+        is_final: bool,
+    ) -> Result<Vec<u8>> {
+        if record.len() < HEADER_SIZE + TAG_SIZE {
+            return Err(Error::Truncated {
+                expected: HEADER_SIZE + TAG_SIZE,
+                actual: record.len(),
+                chunk: Some(sequence),
+            });
+        }
+
+        let (sealed, tag) = record.split_at(record.len() - TAG_SIZE);
+        let mut mac = HmacSha256::new_from_slice(&self.mac).expect("HMAC accepts keys of any size");
+        mac.update(sealed);
+        mac.verify_slice(tag).map_err(|_| Error::Verification {
+            algorithm,
+            chunk: Some(sequence),
+        })?;
+
+        let (header, ciphertext) = sealed.split_at(HEADER_SIZE);
+        if header != encode_header(sequence, is_final).as_slice() {
+            return Err(Error::Verification {
+                algorithm,
+                chunk: Some(sequence),
+            });
+        }
+
+        let mut chunk = ciphertext.to_vec();
+        apply_keystream(&self.cipher, sequence, &mut chunk);
+        Ok(chunk)
+    }
+}
+
+/// Encodes the sequence number of a record and the flag marking the final one.
+fn encode_header(sequence: usize, is_final: bool) -> Vec<u8> {
+    let mut header = Vec::with_capacity(HEADER_SIZE);
+    header.extend((sequence as u64).to_le_bytes());
+    header.push(match is_final {
+        true => FINAL_RECORD,
+        false => INNER_RECORD,
+    });
+    header
+}
+
+/// XORs `data` with the keystream `HMAC(key, sequence || block)` of the record.
+fn apply_keystream(key: &[u8], sequence: usize, data: &mut [u8]) {
+    let sequence = (sequence as u64).to_le_bytes();
+    for (block, chunk) in data.chunks_mut(TAG_SIZE).enumerate() {
+        let pad = hmac(key, &[&sequence, &(block as u64).to_le_bytes()]);
+        chunk
+            .iter_mut()
+            .zip(pad)
+            .for_each(|(byte, pad)| *byte ^= pad);
+    }
+}
+
+/// Computes the HMAC-SHA256 tag over the concatenation of `parts`.
+fn hmac(key: &[u8], parts: &[&[u8]]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any size");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().to_vec()
+}
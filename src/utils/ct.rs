@@ -0,0 +1,91 @@
+//! Helpers for code paths that handle secret values.
+//!
+//! The byte helpers never branch on the data they inspect. The exponentiation
+//! runs the same sequence of multiplications for every exponent of a given
+//! width, although the underlying `ibig` arithmetic is not itself constant-time.
+
+use std::hint::black_box;
+
+use ibig::UBig;
+
+use super::to_fixed_le_bytes;
+
+/// Returns `0xff` if `choice` is set and `0x00` otherwise.
+#[inline]
+fn mask(choice: bool) -> u8 {
+    black_box((choice as u8).wrapping_neg())
+}
+
+/// Compares two byte slices in time that depends only on their lengths.
+pub(crate) fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    black_box(diff) == 0
+}
+
+/// Overwrites `dst` with `src` if `choice` is set, touching every byte either way.
+///
+/// Both slices must have the same length.
+fn ct_assign(dst: &mut [u8], src: &[u8], choice: bool) {
+    debug_assert_eq!(dst.len(), src.len());
+
+    let mask = mask(choice);
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d ^= mask & (*d ^ *s);
+    }
+}
+
+/// Returns a copy of `a` if `choice` is set and a copy of `b` otherwise.
+///
+/// Both slices must have the same length.
+pub(crate) fn ct_select(a: &[u8], b: &[u8], choice: bool) -> Vec<u8> {
+    let mut result = b.to_vec();
+    ct_assign(&mut result, a, choice);
+    result
+}
+
+/// Exchanges `a` and `b` if `choice` is set, rewriting both either way.
+///
+/// Both values must fit into `width` bytes.
+fn ct_swap(a: &mut UBig, b: &mut UBig, width: usize, choice: bool) {
+    let mut x = to_fixed_le_bytes(a, width);
+    let mut y = to_fixed_le_bytes(b, width);
+
+    let mask = mask(choice);
+    for (x, y) in x.iter_mut().zip(y.iter_mut()) {
+        let t = mask & (*x ^ *y);
+        *x ^= t;
+        *y ^= t;
+    }
+
+    *a = UBig::from_le_bytes(&x);
+    *b = UBig::from_le_bytes(&y);
+}
+
+/// Computes `base ^ exponent mod modulus` with a Montgomery ladder.
+///
+/// Every bit position up to the bit length of the modulus is processed with
+/// one multiplication and one squaring, so neither the value nor the length
+/// of the exponent changes the sequence of operations. The bits only drive
+/// conditional swaps, never which operand is read or written. The exponent
+/// must be smaller than `2 ^ modulus.bit_len()`.
+pub(crate) fn ct_powmod(base: &UBig, exponent: &UBig, modulus: &UBig) -> UBig {
+    debug_assert!(exponent.bit_len() <= modulus.bit_len());
+
+    let width = modulus.bit_len().div_ceil(8);
+    let mut r0 = UBig::from(1u8) % modulus;
+    let mut r1 = base % modulus;
+    for i in (0..modulus.bit_len()).rev() {
+        let bit = black_box(exponent.bit(i));
+
+        ct_swap(&mut r0, &mut r1, width, bit);
+        r1 = (&r0 * &r1) % modulus;
+        r0 = (&r0 * &r0) % modulus;
+        ct_swap(&mut r0, &mut r1, width, bit);
+    }
+
+    r0
+}
@@ -1,8 +1,62 @@
+use crate::result::{Error, Result};
+
+/// Version of the wire format produced by [`marshal_bytes`].
+pub(crate) const FORMAT_VERSION: u8 = 1;
+
+/// Size of the length field that precedes every chunk.
+const LENGTH_SIZE: usize = 8;
+
+/// Identifies the type of a value encoded by the typed `to_bytes` methods,
+/// so that bytes of one type are not silently decoded as another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub(crate) enum TypeTag {
+    RsaCiphertext = 0x01,
+    RsaSignature = 0x02,
+    RabinCiphertext = 0x03,
+    RabinSignature = 0x04,
+    ElGamalCiphertext = 0x05,
+    ElGamalSignature = 0x06,
+    PaillierCiphertext = 0x07,
+    DamgardJurikCiphertext = 0x08,
+    GoldwasserMicaliCiphertext = 0x09,
+    OkamotoUchiyamaCiphertext = 0x0a,
+    CramerShoupCiphertext = 0x0b,
+}
+
+/// Prefixes the encoded value with its [`TypeTag`].
+pub(crate) fn tag_bytes(tag: TypeTag, bytes: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(1 + bytes.len());
+    result.push(tag as u8);
+    result.extend(bytes);
+    result
+}
+
+/// Strips the [`TypeTag`] in front of an encoded value and returns the rest.
+///
+/// # Errors
+///
+/// * [`Error::MalformedInput`] if the input is empty or carries another tag.
+pub(crate) fn untag_bytes(tag: TypeTag, bytes: &[u8]) -> Result<&[u8]> {
+    match bytes.split_first() {
+        Some((&actual, rest)) if actual == tag as u8 => Ok(rest),
+        Some(_) => Err(Error::MalformedInput {
+            reason: "type tag does not match",
+            chunk: None,
+        }),
+        None => Err(Error::MalformedInput {
+            reason: "missing type tag",
+            chunk: None,
+        }),
+    }
+}
+
 /// Marshals a vector of vectors of bytes into a single byte vector.
 ///
 /// Each inner vector represents a chunk of bytes to be marshalled.
-/// The resulting byte vector is formatted such that each chunk is prefixed
-/// with an 8-byte length field in big-endian format.
+/// The resulting byte vector starts with a single [`FORMAT_VERSION`] byte,
+/// followed by the chunks, each prefixed with an 8-byte length field in
+/// little-endian format.
 ///
 /// # Arguments
 ///
@@ -14,7 +68,8 @@
 ///
 pub(crate) fn marshal_bytes(bytes: &Vec<Vec<u8>>) -> Vec<u8> {
     let byte_length = bytes.iter().fold(0, |acc, e| acc + e.len());
-    let mut result = Vec::with_capacity(byte_length + bytes.len() * 8);
+    let mut result = Vec::with_capacity(1 + byte_length + bytes.len() * LENGTH_SIZE);
+    result.push(FORMAT_VERSION);
 
     for b in bytes {
         let len = b.len() as u64;
@@ -28,8 +83,9 @@ pub(crate) fn marshal_bytes(bytes: &Vec<Vec<u8>>) -> Vec<u8> {
 /// Unmarshals a slice of raw bytes into a vector of vectors of bytes.
 ///
 /// Each inner vector represents a chunk of bytes extracted from the raw byte slice.
-/// The input `raw_bytes` slice is expected to be formatted such that each chunk is prefixed
-/// with an 8-byte length field in big-endian format.
+/// The input `raw_bytes` slice is expected to be formatted as produced by
+/// [`marshal_bytes`]: a version byte followed by chunks, each prefixed
+/// with an 8-byte length field in little-endian format.
 ///
 /// # Arguments
 ///
@@ -39,18 +95,58 @@ pub(crate) fn marshal_bytes(bytes: &Vec<Vec<u8>>) -> Vec<u8> {
 ///
 /// A vector of vectors of bytes, where each inner vector contains a chunk of bytes.
 ///
-pub(crate) fn unmarshal_bytes(raw_bytes: &[u8]) -> Vec<Vec<u8>> {
-    let mut result = Vec::with_capacity(raw_bytes.len());
+/// # Errors
+///
+/// * [`Error::MalformedInput`] if the input is empty.
+/// * [`Error::UnsupportedFormat`] if the version byte is not [`FORMAT_VERSION`].
+/// * [`Error::Truncated`] if a length field is cut short or a chunk claims
+///   more bytes than remain in the input.
+///
+pub(crate) fn unmarshal_bytes(raw_bytes: &[u8]) -> Result<Vec<Vec<u8>>> {
+    let (&version, mut rest) = raw_bytes.split_first().ok_or(Error::MalformedInput {
+        reason: "missing format version",
+        chunk: None,
+    })?;
 
-    let mut offset = 0;
-    while offset < raw_bytes.len() {
-        let len = u64::from_le_bytes(raw_bytes[offset..offset + 8].try_into().unwrap()) as usize;
-        offset += 8;
+    if version != FORMAT_VERSION {
+        return Err(Error::UnsupportedFormat {
+            expected: FORMAT_VERSION,
+            actual: version,
+        });
+    }
+
+    let mut result = Vec::with_capacity(rest.len() / LENGTH_SIZE);
+    while !rest.is_empty() {
+        let chunk = Some(result.len());
+        if rest.len() < LENGTH_SIZE {
+            return Err(Error::Truncated {
+                expected: LENGTH_SIZE,
+                actual: rest.len(),
+                chunk,
+            });
+        }
+
+        let (len, tail) = rest.split_at(LENGTH_SIZE);
+        let len = u64::from_le_bytes(len.try_into().map_err(|_| Error::MalformedInput {
+            reason: "invalid length field",
+            chunk,
+        })?);
 
-        let bytes = raw_bytes[offset..offset + len].to_vec();
-        result.push(bytes);
-        offset += len;
+        // Lengths are checked against the remaining input before anything is
+        // allocated, so a forged length field cannot trigger a huge allocation.
+        let len = usize::try_from(len).unwrap_or(usize::MAX);
+        if len > tail.len() {
+            return Err(Error::Truncated {
+                expected: len,
+                actual: tail.len(),
+                chunk,
+            });
+        }
+
+        let (bytes, tail) = tail.split_at(len);
+        result.push(bytes.to_vec());
+        rest = tail;
     }
 
-    result
+    Ok(result)
 }
@@ -1,18 +1,58 @@
+mod authenticated;
+mod ct;
 mod marshal;
-mod to_bytes;
+mod modular;
+mod prime;
+mod space;
 
+pub(crate) use authenticated::{RecordKeys, RECORD_SIZE, SECRET_SIZE};
+pub(crate) use ct::{ct_eq, ct_powmod, ct_select};
 use ibig::ops::RemEuclid;
 use ibig::{IBig, UBig};
-pub(crate) use marshal::{marshal_bytes, unmarshal_bytes};
-
-pub(crate) fn imod_inverse(a: &UBig, m: &UBig) -> IBig {
-    a.extended_gcd(m).1
-}
+pub(crate) use marshal::{marshal_bytes, tag_bytes, unmarshal_bytes, untag_bytes, TypeTag};
+pub(crate) use modular::{jacobi, PowMod};
+pub(crate) use prime::{
+    check_prime_bits, gen_prime, gen_prime_pair, has_small_factor, is_probable_prime,
+    MIN_MODULUS_BITS, VALIDATION_ROUNDS,
+};
+pub(crate) use space::CiphertextSpace;
 
 pub(crate) fn imod(a: &IBig, m: &UBig) -> UBig {
     UBig::try_from(a.rem_euclid(IBig::from(m))).unwrap()
 }
 
+/// Converts a hash into an integer of at most `bit_length` bits.
+///
+/// The hash is read as a big-endian number and, when it is wider than
+/// `bit_length`, only its leftmost `bit_length` bits are kept.
+pub(crate) fn bits_to_int(hash: &[u8], bit_length: usize) -> UBig {
+    let value = UBig::from_be_bytes(hash);
+    let hash_bits = hash.len() * 8;
+
+    match hash_bits > bit_length {
+        true => value >> (hash_bits - bit_length),
+        false => value,
+    }
+}
+
+/// Encodes the number as exactly `length` little-endian bytes.
+///
+/// The value must fit into `length` bytes.
+pub(crate) fn to_fixed_le_bytes(value: &UBig, length: usize) -> Vec<u8> {
+    let mut bytes = value.to_le_bytes();
+    bytes.resize(length, 0);
+    bytes
+}
+
+/// Encodes the number as exactly `length` big-endian bytes.
+///
+/// The value must fit into `length` bytes.
+pub(crate) fn to_fixed_be_bytes(value: &UBig, length: usize) -> Vec<u8> {
+    let mut bytes = to_fixed_le_bytes(value, length);
+    bytes.reverse();
+    bytes
+}
+
 pub(crate) fn mod_sub(a: &UBig, b: &UBig, m: &UBig) -> UBig {
     let a = a % m;
     let b = b % m;
@@ -0,0 +1,49 @@
+use ibig::modular::ModuloRing;
+use ibig::{ubig, UBig};
+
+/// Modular exponentiation on top of `ibig`'s modular arithmetic.
+pub(crate) trait PowMod {
+    /// Computes `self ^ exponent mod modulus`.
+    fn powmod(&self, exponent: UBig, modulus: &Self) -> Self;
+}
+
+impl PowMod for UBig {
+    #[inline]
+    fn powmod(&self, exponent: UBig, modulus: &UBig) -> UBig {
+        let ring = ModuloRing::new(modulus);
+        ring.from(self).pow(&exponent).residue()
+    }
+}
+
+/// Computes the Jacobi symbol `(a / n)` for an odd positive `n`.
+///
+/// Returns `1` or `-1`, or `0` if `a` and `n` share a factor. For a prime `n`
+/// this is the Legendre symbol, which tells quadratic residues apart.
+pub(crate) fn jacobi(a: &UBig, n: &UBig) -> i8 {
+    debug_assert!(n % 2u8 == 1u8);
+
+    let mut a = a % n;
+    let mut n = n.clone();
+    let mut result = 1;
+
+    while a != ubig!(0) {
+        while &a % 2u8 == 0u8 {
+            a >>= 1;
+            if matches!(&n % 8u8, 3 | 5) {
+                result = -result;
+            }
+        }
+
+        std::mem::swap(&mut a, &mut n);
+        if &a % 4u8 == 3u8 && &n % 4u8 == 3u8 {
+            result = -result;
+        }
+
+        a %= &n;
+    }
+
+    match n == ubig!(1) {
+        true => result,
+        false => 0,
+    }
+}
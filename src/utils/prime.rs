@@ -0,0 +1,121 @@
+use ibig::{ubig, UBig};
+use rand::{CryptoRng, Rng, RngCore};
+
+use crate::result::{Algorithm, Error, Result};
+use crate::utils::PowMod;
+
+/// Smallest modulus accepted by the `validate` methods of the public keys.
+pub(crate) const MIN_MODULUS_BITS: usize = 128;
+
+/// Smallest prime size accepted by key generation.
+///
+/// Below it, the filters and distinctness checks leave so few candidates
+/// that generation could loop forever.
+pub(crate) const MIN_PRIME_BITS: usize = 16;
+
+/// Number of Miller–Rabin rounds used when validating keys.
+pub(crate) const VALIDATION_ROUNDS: usize = 32;
+
+/// Odd numbers below this bound are tried as divisors by [`has_small_factor`].
+const SMALL_FACTOR_BOUND: u32 = 1000;
+
+/// Checks whether the number is divisible by 2 or by an odd number below [`SMALL_FACTOR_BOUND`],
+/// other than itself.
+pub(crate) fn has_small_factor(n: &UBig) -> bool {
+    if n % 2u8 == 0u8 {
+        return *n != ubig!(2);
+    }
+
+    (3..SMALL_FACTOR_BOUND)
+        .step_by(2)
+        .any(|d| n % d == 0u32 && *n != UBig::from(d))
+}
+
+/// Runs the Miller–Rabin probabilistic primality test with `rounds` random bases.
+///
+/// The bases are drawn from `rng`, so key generation and validation stay
+/// reproducible under a seeded generator.
+pub(crate) fn is_probable_prime<R: Rng + ?Sized>(n: &UBig, rounds: usize, rng: &mut R) -> bool {
+    if *n < ubig!(4) {
+        return *n >= ubig!(2);
+    }
+
+    if has_small_factor(n) {
+        return false;
+    }
+
+    let n_minus_one = n - 1u8;
+    let shift = n_minus_one.trailing_zeros().unwrap_or(0);
+    let odd = &n_minus_one >> shift;
+
+    'witness: for _ in 0..rounds {
+        let base = rng.gen_range(ubig!(2)..n_minus_one.clone());
+        let mut x = base.powmod(odd.clone(), n);
+        if x == ubig!(1) || x == n_minus_one {
+            continue;
+        }
+
+        for _ in 1..shift {
+            x = (&x * &x) % n;
+            if x == n_minus_one {
+                continue 'witness;
+            }
+        }
+
+        return false;
+    }
+
+    true
+}
+
+/// Ensures that key generation for `algorithm` can draw primes of `bit_length` bits.
+pub(crate) fn check_prime_bits(algorithm: Algorithm, bit_length: usize) -> Result<()> {
+    match bit_length >= MIN_PRIME_BITS {
+        true => Ok(()),
+        false => Err(Error::KeyGeneration {
+            algorithm,
+            reason: "primes would be too small",
+        }),
+    }
+}
+
+/// Draws random odd numbers of exactly `bit_length` bits from `rng` until one
+/// passes `rounds` rounds of Miller–Rabin and satisfies `accept`.
+///
+/// Every random choice, including the Miller–Rabin bases, comes from `rng`,
+/// so a seeded generator always yields the same prime.
+pub(crate) fn gen_prime<R, F>(bit_length: usize, rounds: usize, rng: &mut R, accept: F) -> UBig
+where
+    R: RngCore + CryptoRng + ?Sized,
+    F: Fn(&UBig) -> bool,
+{
+    let bit_length = bit_length.max(2);
+    loop {
+        let mut candidate: UBig = rng.gen_range(ubig!(0)..ubig!(1) << bit_length);
+        candidate.set_bit(bit_length - 1);
+        candidate.set_bit(0);
+
+        if accept(&candidate) && is_probable_prime(&candidate, rounds, rng) {
+            break candidate;
+        }
+    }
+}
+
+/// Draws the two distinct primes of `bit_length` bits behind a modulus `n`.
+///
+/// Primes of equal length guarantee gcd(n, λ) = 1 as long as they differ.
+pub(crate) fn gen_prime_pair<R: RngCore + CryptoRng>(
+    bit_length: usize,
+    persistence: usize,
+    rng: &mut R,
+) -> (UBig, UBig) {
+    let p = gen_prime(bit_length, persistence, rng, |_| true);
+    let q = loop {
+        let q: UBig = gen_prime(bit_length, persistence, rng, |_| true);
+        if q != p {
+            break q;
+        }
+    };
+
+    (p, q)
+}
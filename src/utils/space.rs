@@ -0,0 +1,108 @@
+use ibig::{ubig, UBig};
+use rand::{CryptoRng, Rng, RngCore};
+
+use crate::result::{Algorithm, Error, Result};
+use crate::utils::{has_small_factor, PowMod, MIN_MODULUS_BITS};
+
+/// The arithmetic shared by Paillier and Damgård–Jurik, of which Paillier is the case `s = 1`.
+///
+/// Plaintexts live modulo `n ^ s` and ciphertexts modulo `n ^ (s + 1)`. Errors are
+/// reported under the `algorithm` of the key the space belongs to.
+pub(crate) struct CiphertextSpace<'a> {
+    pub(crate) algorithm: Algorithm,
+    pub(crate) divisor: &'a UBig,
+    pub(crate) degree: usize,
+}
+
+impl CiphertextSpace<'_> {
+    /// Checks that the modulus is odd, at least `MIN_MODULUS_BITS` wide and free of
+    /// small factors, and that the degree is at least 1.
+    pub(crate) fn validate(&self) -> Result<()> {
+        let invalid = |reason| {
+            Err(Error::InvalidKey {
+                algorithm: self.algorithm,
+                reason,
+            })
+        };
+
+        if self.divisor.bit_len() < MIN_MODULUS_BITS {
+            return invalid("modulus is too small");
+        }
+
+        if self.divisor % 2u8 == 0u8 {
+            return invalid("modulus is even");
+        }
+
+        if has_small_factor(self.divisor) {
+            return invalid("modulus has a small factor");
+        }
+
+        if self.degree == 0 {
+            return invalid("degree is zero");
+        }
+
+        Ok(())
+    }
+
+    /// Returns `n ^ s`, the size of the plaintext space.
+    #[inline]
+    pub(crate) fn plaintext_modulus(&self) -> UBig {
+        self.divisor.pow(self.degree)
+    }
+
+    /// Returns `n ^ (s + 1)`, the modulus ciphertexts live in.
+    #[inline]
+    pub(crate) fn ciphertext_modulus(&self) -> UBig {
+        self.divisor.pow(self.degree + 1)
+    }
+
+    /// Ensures that the ciphertext lies below `n ^ (s + 1)`.
+    pub(crate) fn check_ciphertext(&self, ciphertext: &UBig) -> Result<()> {
+        match *ciphertext < self.ciphertext_modulus() {
+            true => Ok(()),
+            false => Err(Error::OutOfRange {
+                algorithm: self.algorithm,
+                reason: "ciphertext is not smaller than n ^ (s + 1)",
+            }),
+        }
+    }
+
+    /// Draws a random `r ^ (n ^ s) mod n ^ (s + 1)` with `r` invertible modulo `n`,
+    /// which is an encryption of zero.
+    pub(crate) fn random_mask<R: RngCore + CryptoRng>(&self, rng: &mut R) -> UBig {
+        let r = loop {
+            let r = rng.gen_range(ubig!(1)..self.divisor.clone());
+            if r.gcd(self.divisor) == ubig!(1) {
+                break r;
+            }
+        };
+
+        r.powmod(self.plaintext_modulus(), &self.ciphertext_modulus())
+    }
+
+    /// Multiplies two ciphertexts, which adds their plaintexts modulo `n ^ s`.
+    pub(crate) fn mul(&self, a: &UBig, b: &UBig) -> Result<UBig> {
+        self.check_ciphertext(a)?;
+        self.check_ciphertext(b)?;
+
+        Ok((a * b) % self.ciphertext_modulus())
+    }
+
+    /// Raises a ciphertext to `exponent`, which multiplies its plaintext by the scalar.
+    pub(crate) fn pow(&self, ciphertext: &UBig, exponent: &UBig) -> Result<UBig> {
+        self.check_ciphertext(ciphertext)?;
+
+        Ok(ciphertext.powmod(exponent.clone(), &self.ciphertext_modulus()))
+    }
+
+    /// Multiplies the ciphertext with a fresh encryption of zero.
+    pub(crate) fn rerandomize<R: RngCore + CryptoRng>(
+        &self,
+        ciphertext: &UBig,
+        rng: &mut R,
+    ) -> Result<UBig> {
+        self.check_ciphertext(ciphertext)?;
+
+        Ok((ciphertext * self.random_mask(rng)) % self.ciphertext_modulus())
+    }
+}
@@ -1,4 +1,9 @@
-use crate::utils::{marshal_bytes, unmarshal_bytes};
+use ibig::UBig;
+
+use crate::result::{Algorithm, Error};
+use crate::utils::{
+    ct_eq, ct_powmod, ct_select, is_probable_prime, jacobi, marshal_bytes, unmarshal_bytes, PowMod,
+};
 
 #[test]
 fn test_marshal_unmarshal() {
@@ -6,7 +11,131 @@ fn test_marshal_unmarshal() {
     let chunks: Vec<Vec<u8>> = MESSAGE.chunks(64).map(|chunk| chunk.to_vec()).collect();
 
     let marshalled = marshal_bytes(&chunks);
-    let unmarshalled: Vec<u8> = unmarshal_bytes(&marshalled).into_iter().flatten().collect();
+    let unmarshalled: Vec<u8> = unmarshal_bytes(&marshalled)
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect();
 
     assert_eq!(MESSAGE, unmarshalled.as_slice());
 }
+
+#[test]
+fn test_unmarshal_rejects_malformed() {
+    let marshalled = marshal_bytes(&vec![b"Lorem".to_vec(), b"ipsum".to_vec()]);
+
+    assert!(matches!(
+        unmarshal_bytes(&[]),
+        Err(Error::MalformedInput { .. })
+    ));
+
+    let truncated = &marshalled[..marshalled.len() - 1];
+    assert_eq!(
+        unmarshal_bytes(truncated),
+        Err(Error::Truncated {
+            expected: 5,
+            actual: 4,
+            chunk: Some(1)
+        })
+    );
+
+    let cut_length = &marshalled[..4];
+    assert_eq!(
+        unmarshal_bytes(cut_length),
+        Err(Error::Truncated {
+            expected: 8,
+            actual: 3,
+            chunk: Some(0)
+        })
+    );
+
+    let mut oversized = marshalled.clone();
+    oversized[1..9].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert!(matches!(
+        unmarshal_bytes(&oversized),
+        Err(Error::Truncated { chunk: Some(0), .. })
+    ));
+
+    let mut versioned = marshalled;
+    versioned[0] = 0xff;
+    assert_eq!(
+        unmarshal_bytes(&versioned),
+        Err(Error::UnsupportedFormat {
+            expected: 1,
+            actual: 0xff
+        })
+    );
+}
+
+#[test]
+fn test_error_into_io_error() {
+    let error = Error::Verification {
+        algorithm: Algorithm::Rsa,
+        chunk: Some(3),
+    };
+    assert_eq!(error.to_string(), "RSA verification failed in chunk 3");
+
+    let error: std::io::Error = error.into();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_ct_helpers() {
+    assert!(ct_eq(b"Lorem", b"Lorem"));
+    assert!(!ct_eq(b"Lorem", b"Lorex"));
+    assert!(!ct_eq(b"Lorem", b"Lore"));
+
+    assert_eq!(ct_select(b"Lorem", b"ipsum", true), b"Lorem");
+    assert_eq!(ct_select(b"Lorem", b"ipsum", false), b"ipsum");
+
+    let modulus = UBig::from(1_000_000_007u32);
+    for (base, exponent) in [
+        (2u32, 0u32),
+        (3, 1),
+        (12345, 67890),
+        (999_999, 1_000_000_005),
+    ] {
+        let (base, exponent) = (UBig::from(base), UBig::from(exponent));
+        assert_eq!(
+            ct_powmod(&base, &exponent, &modulus),
+            base.powmod(exponent.clone(), &modulus)
+        );
+    }
+}
+
+#[test]
+fn test_is_probable_prime() {
+    let mut rng = rand::thread_rng();
+
+    for prime in [2u32, 3, 5, 997, 1009, 1_000_000_007] {
+        assert!(is_probable_prime(&UBig::from(prime), 16, &mut rng));
+    }
+
+    // 561 and 41041 are Carmichael numbers
+    for composite in [0u32, 1, 4, 561, 41041, 1_000_000_011 * 3] {
+        assert!(!is_probable_prime(&UBig::from(composite), 16, &mut rng));
+    }
+}
+
+#[test]
+fn test_jacobi() {
+    // Values from the standard table of Jacobi symbols
+    for (a, n, expected) in [
+        (1u32, 1u32, 1i8),
+        (2, 7, 1),
+        (3, 7, -1),
+        (5, 9, 1),
+        (2, 15, 1),
+        (7, 15, -1),
+        (6, 15, 0),
+        (1001, 9907, -1),
+        (19, 45, 1),
+        (8, 21, -1),
+    ] {
+        assert_eq!(
+            jacobi(&UBig::from(a), &UBig::from(n)),
+            expected,
+            "({a} / {n})"
+        );
+    }
+}